@@ -0,0 +1,431 @@
+//! Parsing of the `#[db_enum(...)]` attribute, along with the legacy
+//! top-level attributes (`PgType`, `DieselType`, ...) that it replaces.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::meta::ParseNestedMeta;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::token::Paren;
+use syn::{Attribute, Error, Expr, ExprLit, Ident, Lit, LitStr, Meta, MetaNameValue, Path, Result};
+
+use crate::integer::IntRepr;
+use crate::{CaseStyle, Errors};
+
+/// Options that apply to the whole enum
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    pub existing_type_path: Vec<Path>,
+    pub diesel_type: Option<Ident>,
+    pub pg_type: Option<LitStr>,
+    pub rename_all: Option<(CaseStyle, Span)>,
+    pub backend_rename_all: PerBackend<Option<(CaseStyle, Span)>>,
+    pub prefix: Option<LitStr>,
+    pub suffix: Option<LitStr>,
+    pub strip_prefix: Option<LitStr>,
+    pub strip_suffix: Option<LitStr>,
+    pub repr: Option<(IntRepr, Span)>,
+    pub sql_type: Option<Path>,
+    pub display: Option<Span>,
+    pub from_str: Option<Span>,
+    pub serde: Option<Span>,
+    pub ord: Option<Span>,
+    pub deprecations: Vec<Deprecation>,
+}
+
+impl ContainerAttrs {
+    pub fn from_attrs(attrs: &[Attribute], errors: &mut Errors) -> Self {
+        let mut container = Self::default();
+        for attr in attrs {
+            let Some(name) = attr.path().get_ident() else {
+                continue;
+            };
+            let result = match name.to_string().as_str() {
+                "db_enum" => attr.parse_nested_meta(|meta| container.parse_meta(meta)),
+                "ExistingTypePath" => legacy_value(attr).and_then(|lit| {
+                    container
+                        .deprecations
+                        .push(Deprecation::new(attr, "existing_type_path = ..."));
+                    let path = parse_lit(&lit, "`existing_type_path` must be a valid Rust path")?;
+                    container.push_existing_type_path(path)
+                }),
+                "DieselType" => legacy_value(attr).and_then(|lit| {
+                    container
+                        .deprecations
+                        .push(Deprecation::new(attr, "diesel_type = ..."));
+                    let ident = parse_lit(&lit, "`diesel_type` must be a valid Rust identifier")?;
+                    set(
+                        &mut container.diesel_type,
+                        ident,
+                        name.span(),
+                        "diesel_type",
+                    )
+                }),
+                "PgType" => legacy_value(attr).and_then(|lit| {
+                    container
+                        .deprecations
+                        .push(Deprecation::new(attr, "pg_type = \"...\""));
+                    set(&mut container.pg_type, lit, name.span(), "pg_type")
+                }),
+                "DbValueStyle" => legacy_value(attr).and_then(|lit| {
+                    container
+                        .deprecations
+                        .push(Deprecation::new(attr, "rename_all = \"...\""));
+                    let style = CaseStyle::from_lit(&lit)?;
+                    set(
+                        &mut container.rename_all,
+                        (style, lit.span()),
+                        name.span(),
+                        "rename_all",
+                    )
+                }),
+                _ => Ok(()),
+            };
+            errors.ok(result);
+        }
+        container
+    }
+
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> Result<()> {
+        let span = meta.path.span();
+        if meta.path.is_ident("existing_type_path") {
+            let path = parse_value(
+                meta.value()?,
+                "`existing_type_path` must be a valid Rust path",
+            )?;
+            self.push_existing_type_path(path)
+        } else if meta.path.is_ident("diesel_type") {
+            let ident = parse_value(
+                meta.value()?,
+                "`diesel_type` must be a valid Rust identifier",
+            )?;
+            set(&mut self.diesel_type, ident, span, "diesel_type")
+        } else if meta.path.is_ident("pg_type") {
+            let lit: LitStr = meta.value()?.parse()?;
+            set(&mut self.pg_type, lit, span, "pg_type")
+        } else if meta.path.is_ident("rename_all") && meta.input.peek(Paren) {
+            self.backend_rename_all
+                .parse_meta(&meta, "rename_all", |input| {
+                    let lit: LitStr = input.parse()?;
+                    Ok((CaseStyle::from_lit(&lit)?, lit.span()))
+                })
+        } else if meta.path.is_ident("rename_all") {
+            let lit: LitStr = meta.value()?.parse()?;
+            let style = CaseStyle::from_lit(&lit)?;
+            set(
+                &mut self.rename_all,
+                (style, lit.span()),
+                span,
+                "rename_all",
+            )
+        } else if meta.path.is_ident("prefix") {
+            set(&mut self.prefix, meta.value()?.parse()?, span, "prefix")
+        } else if meta.path.is_ident("suffix") {
+            set(&mut self.suffix, meta.value()?.parse()?, span, "suffix")
+        } else if meta.path.is_ident("strip_prefix") {
+            set(
+                &mut self.strip_prefix,
+                meta.value()?.parse()?,
+                span,
+                "strip_prefix",
+            )
+        } else if meta.path.is_ident("strip_suffix") {
+            set(
+                &mut self.strip_suffix,
+                meta.value()?.parse()?,
+                span,
+                "strip_suffix",
+            )
+        } else if meta.path.is_ident("repr") {
+            let ident = parse_value(meta.value()?, "`repr` must be one of `i16`, `i32`, `i64`")?;
+            let repr = IntRepr::from_ident(&ident)?;
+            set(&mut self.repr, (repr, span), span, "repr")
+        } else if meta.path.is_ident("sql_type") {
+            let path: Path = parse_value(meta.value()?, "`sql_type` must be a valid Rust path")?;
+            if !is_text_type(&path) {
+                return Err(Error::new_spanned(
+                    path,
+                    "`sql_type` must be `Text` or `VarChar`",
+                ));
+            }
+            set(&mut self.sql_type, path, span, "sql_type")
+        } else if meta.path.is_ident("display") {
+            set(&mut self.display, span, span, "display")
+        } else if meta.path.is_ident("from_str") {
+            set(&mut self.from_str, span, span, "from_str")
+        } else if meta.path.is_ident("serde") {
+            if !cfg!(feature = "serde") {
+                return Err(
+                    meta.error("`serde` requires the `serde` feature of `diesel-derive-enum`")
+                );
+            }
+            set(&mut self.serde, span, span, "serde")
+        } else if meta.path.is_ident("ord") {
+            set(&mut self.ord, span, span, "ord")
+        } else {
+            Err(meta.error(
+                "unknown `db_enum` attribute, expected one of `existing_type_path`, \
+                 `diesel_type`, `pg_type`, `rename_all`, `prefix`, `suffix`, `strip_prefix`, \
+                 `strip_suffix`, `repr`, `sql_type`, `display`, `from_str`, `serde`, `ord`",
+            ))
+        }
+    }
+
+    /// Unlike the other options, `existing_type_path` may be given several
+    /// times to bind the enum to each of those types
+    fn push_existing_type_path(&mut self, path: Path) -> Result<()> {
+        let name = quote!(#path).to_string();
+        if self
+            .existing_type_path
+            .iter()
+            .any(|existing| quote!(#existing).to_string() == name)
+        {
+            return Err(Error::new_spanned(
+                path,
+                "this `existing_type_path` is specified more than once",
+            ));
+        }
+        self.existing_type_path.push(path);
+        Ok(())
+    }
+}
+
+/// `VarChar` is only an alias of `Text` in diesel, so either spelling binds
+/// to the same SQL type
+fn is_text_type(path: &Path) -> bool {
+    path.segments.last().is_some_and(|segment| {
+        segment.arguments.is_none()
+            && ["Text", "VarChar", "Varchar"]
+                .iter()
+                .any(|name| segment.ident == name)
+    })
+}
+
+/// Options that apply to a single variant
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+    pub rename: Option<LitStr>,
+    pub backend_rename: PerBackend<Option<LitStr>>,
+    pub alias: Vec<LitStr>,
+    pub other: Option<Span>,
+    pub read_only: Option<Span>,
+    pub skip: Option<Span>,
+    pub value: Option<Expr>,
+    pub deprecations: Vec<Deprecation>,
+}
+
+impl VariantAttrs {
+    pub fn from_attrs(attrs: &[Attribute], errors: &mut Errors) -> Self {
+        let mut variant = Self::default();
+        for attr in attrs {
+            let Some(name) = attr.path().get_ident() else {
+                continue;
+            };
+            let result = match name.to_string().as_str() {
+                "db_enum" => attr.parse_nested_meta(|meta| variant.parse_meta(meta)),
+                "db_rename" => legacy_value(attr).and_then(|lit| {
+                    variant
+                        .deprecations
+                        .push(Deprecation::new(attr, "rename = \"...\""));
+                    set(&mut variant.rename, lit, name.span(), "rename")
+                }),
+                _ => Ok(()),
+            };
+            errors.ok(result);
+        }
+        variant
+    }
+
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> Result<()> {
+        let span = meta.path.span();
+        if meta.path.is_ident("rename") && meta.input.peek(Paren) {
+            self.backend_rename
+                .parse_meta(&meta, "rename", |input| input.parse())
+        } else if meta.path.is_ident("rename") {
+            let lit: LitStr = meta.value()?.parse()?;
+            set(&mut self.rename, lit, span, "rename")
+        } else if meta.path.is_ident("alias") {
+            let lit: LitStr = meta.value()?.parse()?;
+            if self.alias.iter().any(|alias| alias.value() == lit.value()) {
+                return Err(Error::new(
+                    lit.span(),
+                    "this `alias` is specified more than once",
+                ));
+            }
+            self.alias.push(lit);
+            Ok(())
+        } else if meta.path.is_ident("read_only") {
+            set(&mut self.read_only, span, span, "read_only")
+        } else if meta.path.is_ident("skip") {
+            set(&mut self.skip, span, span, "skip")
+        } else if meta.path.is_ident("other") {
+            set(&mut self.other, span, span, "other")
+        } else if meta.path.is_ident("value") {
+            let value: Expr = meta.value()?.parse()?;
+            set(&mut self.value, value, span, "value")
+        } else {
+            Err(meta.error(
+                "unknown `db_enum` variant attribute, expected one of `rename`, `alias`, `other`, `read_only`, \
+                 `skip`, `value`",
+            ))
+        }
+    }
+}
+
+/// A database backend, for options which differ between them
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Backend {
+    Postgres,
+    Mysql,
+    Sqlite,
+}
+
+/// One value for each backend, e.g. `rename(postgres = "...", mysql = "...")`
+#[derive(Default, Clone, Debug, PartialEq)]
+pub(crate) struct PerBackend<T> {
+    pub postgres: T,
+    pub mysql: T,
+    pub sqlite: T,
+}
+
+impl<T> PerBackend<T> {
+    pub fn from_fn(mut f: impl FnMut(Backend) -> T) -> Self {
+        PerBackend {
+            postgres: f(Backend::Postgres),
+            mysql: f(Backend::Mysql),
+            sqlite: f(Backend::Sqlite),
+        }
+    }
+
+    pub fn get(&self, backend: Backend) -> &T {
+        match backend {
+            Backend::Postgres => &self.postgres,
+            Backend::Mysql => &self.mysql,
+            Backend::Sqlite => &self.sqlite,
+        }
+    }
+}
+
+impl<T> PerBackend<Option<T>> {
+    /// Any one of the values that were given
+    pub fn any(&self) -> Option<&T> {
+        self.postgres
+            .as_ref()
+            .or(self.mysql.as_ref())
+            .or(self.sqlite.as_ref())
+    }
+
+    fn parse_meta(
+        &mut self,
+        meta: &ParseNestedMeta,
+        name: &str,
+        parse: impl Fn(ParseStream) -> Result<T>,
+    ) -> Result<()> {
+        meta.parse_nested_meta(|inner| {
+            let span = inner.path.span();
+            let (slot, backend) = if inner.path.is_ident("postgres") {
+                (&mut self.postgres, "postgres")
+            } else if inner.path.is_ident("mysql") {
+                (&mut self.mysql, "mysql")
+            } else if inner.path.is_ident("sqlite") {
+                (&mut self.sqlite, "sqlite")
+            } else {
+                return Err(inner.error(format!(
+                    "unknown backend in `{}`, expected one of `postgres`, `mysql`, `sqlite`",
+                    name
+                )));
+            };
+            let value = parse(inner.value()?)?;
+            set(slot, value, span, &format!("{}({})", name, backend))
+        })
+    }
+}
+
+/// A legacy attribute which still works, but should be replaced by its
+/// `#[db_enum(...)]` equivalent
+pub(crate) struct Deprecation {
+    name: Ident,
+    note: String,
+}
+
+impl Deprecation {
+    fn new(attr: &Attribute, replacement: &str) -> Self {
+        let name = attr
+            .path()
+            .get_ident()
+            .expect("legacy attributes are idents");
+        Deprecation {
+            name: name.clone(),
+            note: format!(
+                "the `{}` attribute is deprecated, use `#[db_enum({})]` instead",
+                name, replacement
+            ),
+        }
+    }
+
+    /// Proc macros can't emit warnings directly, so instead we reference a
+    /// deprecated item spanned to the offending attribute
+    pub fn warning(&self) -> TokenStream {
+        let note = &self.note;
+        let name = &self.name;
+        quote_spanned! {name.span()=>
+            const _: () = {
+                #[deprecated(note = #note)]
+                #[allow(non_upper_case_globals)]
+                const #name: () = ();
+                #name
+            };
+        }
+    }
+}
+
+fn set<T>(slot: &mut Option<T>, value: T, span: Span, name: &str) -> Result<()> {
+    if slot.is_some() {
+        return Err(Error::new(
+            span,
+            format!("`{}` is specified more than once", name),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// Parses a value given either directly (`diesel_type = MyMapping`) or as a
+/// string literal (`diesel_type = "MyMapping"`)
+fn parse_value<T: syn::parse::Parse>(input: ParseStream, message: &str) -> Result<T> {
+    if input.peek(LitStr) {
+        parse_lit(&input.parse()?, message)
+    } else {
+        input
+            .parse()
+            .map_err(|error| Error::new(error.span(), message))
+    }
+}
+
+fn parse_lit<T: syn::parse::Parse>(lit: &LitStr, message: &str) -> Result<T> {
+    lit.parse().map_err(|_| Error::new(lit.span(), message))
+}
+
+/// Legacy attributes all have the form `#[Name = "value"]`
+fn legacy_value(attr: &Attribute) -> Result<LitStr> {
+    match &attr.meta {
+        Meta::NameValue(MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }),
+            ..
+        }) => Ok(lit_str.clone()),
+        _ => {
+            let name = attr
+                .path()
+                .get_ident()
+                .expect("legacy attributes are idents");
+            Err(Error::new_spanned(
+                attr,
+                format!("Attribute '{}' must have form: {} = \"value\"", name, name),
+            ))
+        }
+    }
+}
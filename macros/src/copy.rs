@@ -0,0 +1,100 @@
+//! Arrays of the enum for Postgres' binary `COPY FROM`. Arrays state the OID of
+//! their element type, but `COPY FROM` serializes values without a connection to
+//! look it up with, so the caller looks it up beforehand and passes it along.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+pub(crate) fn generate_copy_array(
+    enum_ty: &Ident,
+    copy_ty: &Ident,
+    pg_mapping: &TokenStream,
+) -> TokenStream {
+    let copy_name = copy_ty.to_string();
+    let metadata_doc = format!(
+        " Looks up the enum's Postgres type through `conn`, for writing arrays of it with \
+         binary `COPY FROM`, see `{}`. The OIDs differ between databases, so only use \
+         it with connections to the same one.",
+        copy_name
+    );
+    quote! {
+        /// An array of the enum for binary `COPY FROM`, e.g. a field of an
+        /// `Insertable` struct passed to `from_insertable`
+        pub struct #copy_ty<'a> {
+            metadata: diesel::pg::PgTypeMetadata,
+            values: &'a [#enum_ty],
+        }
+
+        impl<'a> #copy_ty<'a> {
+            /// `values`, written as an array of the Postgres type described by
+            /// `metadata`, from `pg_type_metadata`
+            pub fn new(metadata: &diesel::pg::PgTypeMetadata, values: &'a [#enum_ty]) -> Self {
+                #copy_ty {
+                    metadata: metadata.clone(),
+                    values,
+                }
+            }
+        }
+
+        impl std::fmt::Debug for #copy_ty<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(#copy_name)
+                    .field("metadata", &self.metadata)
+                    .field("len", &self.values.len())
+                    .finish()
+            }
+        }
+
+        impl<'a> AsExpression<Array<#pg_mapping>> for #copy_ty<'a> {
+            type Expression = Bound<Array<#pg_mapping>, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a, 'b> AsExpression<Array<#pg_mapping>> for &'b #copy_ty<'a> {
+            type Expression = Bound<Array<#pg_mapping>, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl #enum_ty {
+            #[doc = #metadata_doc]
+            pub fn pg_type_metadata(
+                conn: &mut diesel::pg::PgConnection,
+            ) -> diesel::QueryResult<diesel::pg::PgTypeMetadata> {
+                let metadata = <diesel::pg::Pg as HasSqlType<#pg_mapping>>::metadata(conn);
+                match metadata.oid() {
+                    Ok(_) => Ok(metadata),
+                    Err(e) => Err(diesel::result::Error::QueryBuilderError(Box::new(e))),
+                }
+            }
+        }
+    }
+}
+
+/// Goes in the Postgres impls of the mapping, to write the same values as they do
+pub(crate) fn generate_copy_array_to_sql(copy_ty: &Ident, pg_mapping: &TokenStream) -> TokenStream {
+    quote! {
+        // The binary format of `array_recv`, with one dimension and no NULLs
+        impl ToSql<Array<#pg_mapping>, Pg> for #copy_ty<'_> {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                out.write_all(&1i32.to_be_bytes())?;
+                out.write_all(&0i32.to_be_bytes())?;
+                out.write_all(&self.metadata.oid()?.to_be_bytes())?;
+                out.write_all(&i32::try_from(self.values.len())?.to_be_bytes())?;
+                out.write_all(&1i32.to_be_bytes())?;
+                for value in self.values {
+                    check_writable(value)?;
+                    let bytes = db_str_representation(value).as_bytes();
+                    out.write_all(&i32::try_from(bytes.len())?.to_be_bytes())?;
+                    out.write_all(bytes)?;
+                }
+                Ok(IsNull::No)
+            }
+        }
+    }
+}
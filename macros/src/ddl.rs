@@ -0,0 +1,294 @@
+//! Schema definitions (`CREATE TYPE ...`, `enum(...)`, `CHECK (...)`) built
+//! from the enum's database values, so migrations can't drift from the Rust side,
+//! along with checks that the live database still agrees and Postgres' own enum
+//! support functions.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+use crate::attrs::PerBackend;
+
+pub(crate) fn generate_ddl_fns(
+    enum_ty: &Ident,
+    pg_type_name: Option<&str>,
+    backend_values: &PerBackend<&[String]>,
+    aliases: &[Vec<String>],
+    pg_mapping: Option<&TokenStream>,
+) -> TokenStream {
+    let enum_name = enum_ty.to_string();
+    let all_aliases: Vec<&String> = aliases.iter().flatten().collect();
+    let pg_fns = if cfg!(feature = "postgres") {
+        let variants_db = backend_values.postgres;
+        // Without the type's name, there's nothing to declare or alter
+        let type_fns = pg_type_name.map(|pg_type_name| {
+            let labels = literal_list(variants_db, ", ", quote_literal);
+            let create = format!(
+                "CREATE TYPE {} AS ENUM ({})",
+                quote_identifier(pg_type_name),
+                labels
+            );
+            let drop = format!("DROP TYPE {}", quote_identifier(pg_type_name));
+            // Once renamed, the other aliases of a variant can't be renamed to the
+            // same value, so only the first is
+            let renames = variants_db
+                .iter()
+                .zip(aliases)
+                .filter_map(|(value, aliases)| {
+                    Some(format!(
+                        "ALTER TYPE {} RENAME VALUE {} TO {}",
+                        quote_identifier(pg_type_name),
+                        quote_literal(aliases.first()?),
+                        quote_literal(value)
+                    ))
+                })
+                .collect::<Vec<_>>()
+                .join(";\n");
+            quote! {
+                /// The statement creating this enum's Postgres type
+                pub fn pg_create_type_sql() -> &'static str {
+                    #create
+                }
+
+                /// The statement dropping this enum's Postgres type
+                pub fn pg_drop_type_sql() -> &'static str {
+                    #drop
+                }
+
+                /// The statements renaming the first alias of each variant in the Postgres
+                /// type to its value, which also updates every row using it
+                pub fn pg_rename_aliases_sql() -> &'static str {
+                    #renames
+                }
+            }
+        });
+        let updates = alias_updates(variants_db, aliases, quote_literal);
+        // Only a Postgres enum type has these, not `Text`, and they need its name
+        let enum_support_fns = pg_mapping.zip(pg_type_name).map(|(mapping, pg_type_name)| {
+            let null = format!("NULL::{}", quote_identifier(pg_type_name));
+            let range = format!("enum_range({})", null);
+            let first = format!("enum_first({})", null);
+            let last = format!("enum_last({})", null);
+            quote! {
+                /// `enum_range()`, every label of the Postgres type in order, e.g.
+                /// `diesel::select(MyEnum::pg_enum_range()).get_result::<Vec<MyEnum>>(conn)`
+                pub fn pg_enum_range() -> diesel::expression::SqlLiteral<
+                    diesel::sql_types::Array<#mapping>,
+                > {
+                    diesel::dsl::sql(#range)
+                }
+
+                /// `enum_first()`, the first label of the Postgres type
+                pub fn pg_enum_first() -> diesel::expression::SqlLiteral<#mapping> {
+                    diesel::dsl::sql(#first)
+                }
+
+                /// `enum_last()`, the last label of the Postgres type
+                pub fn pg_enum_last() -> diesel::expression::SqlLiteral<#mapping> {
+                    diesel::dsl::sql(#last)
+                }
+            }
+        });
+        // The mapping type knows which Postgres type it stands for, even when
+        // the derive doesn't know its name. A type which can't be found has no
+        // labels, so is reported as missing every value. Enums only bound to
+        // `Text` have no Postgres type to compare with.
+        let verify_fn = pg_mapping.map(|mapping| {
+            quote! {
+                /// Compares the enum's values with the labels of its Postgres type
+                pub fn pg_verify_schema(
+                    conn: &mut diesel::pg::PgConnection,
+                ) -> diesel::QueryResult<::diesel_derive_enum::SchemaReport> {
+                    use diesel::RunQueryDsl;
+
+                    let metadata =
+                        <diesel::pg::Pg as diesel::sql_types::HasSqlType<#mapping>>::metadata(conn);
+                    let labels = match metadata.oid() {
+                        Ok(oid) => diesel::select(
+                            diesel::dsl::sql::<diesel::sql_types::Array<diesel::sql_types::Text>>(
+                                "ARRAY(SELECT enumlabel::text FROM pg_enum WHERE enumtypid = ",
+                            )
+                            .bind::<diesel::sql_types::Oid, _>(oid)
+                            .sql(" ORDER BY enumsortorder)"),
+                        )
+                        .get_result::<Vec<String>>(conn)?,
+                        Err(_) => Vec::new(),
+                    };
+                    Ok(::diesel_derive_enum::SchemaReport::new(
+                        #enum_name,
+                        &[#(#variants_db),*],
+                        &[#(#all_aliases),*],
+                        &labels,
+                    ))
+                }
+            }
+        });
+        Some(quote! {
+            #enum_support_fns
+            #type_fns
+            #verify_fn
+
+            /// The statements updating rows of `table` which still hold an alias in
+            /// `column` to use the value instead
+            pub fn pg_update_aliases_sql(table: &str, column: &str) -> String {
+                ::diesel_derive_enum::__private::update_aliases_sql(table, column, '"', &[#(#updates),*])
+            }
+        })
+    } else {
+        None
+    };
+
+    let mysql_fns = if cfg!(feature = "mysql") {
+        let variants_db = backend_values.mysql;
+        let updates = alias_updates(variants_db, aliases, quote_mysql_literal);
+        let column_type = format!(
+            "enum({})",
+            literal_list(variants_db, ",", quote_mysql_literal)
+        );
+        Some(quote! {
+            /// The MySQL column type holding this enum, e.g. `enum('foo','bar')`
+            pub fn mysql_column_type_sql() -> &'static str {
+                #column_type
+            }
+
+            /// The statements updating rows of `table` which still hold an alias in
+            /// `column` to use the value instead
+            pub fn mysql_update_aliases_sql(table: &str, column: &str) -> String {
+                ::diesel_derive_enum::__private::update_aliases_sql(table, column, '`', &[#(#updates),*])
+            }
+
+            /// Compares the enum's values with the type of a MySQL column
+            pub fn mysql_verify_schema(
+                conn: &mut diesel::mysql::MysqlConnection,
+                table: &str,
+                column: &str,
+            ) -> diesel::QueryResult<::diesel_derive_enum::SchemaReport> {
+                use diesel::RunQueryDsl;
+
+                let column_type = diesel::select(
+                    diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Text>>(
+                        "(SELECT COLUMN_TYPE FROM information_schema.COLUMNS \
+                         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ",
+                    )
+                    .bind::<diesel::sql_types::Text, _>(table)
+                    .sql(" AND COLUMN_NAME = ")
+                    .bind::<diesel::sql_types::Text, _>(column)
+                    .sql(")"),
+                )
+                .get_result::<Option<String>>(conn)?;
+                let labels = column_type
+                    .as_deref()
+                    .and_then(::diesel_derive_enum::__private::parse_mysql_enum)
+                    .unwrap_or_default();
+                Ok(::diesel_derive_enum::SchemaReport::new(
+                    #enum_name,
+                    &[#(#variants_db),*],
+                    &[#(#all_aliases),*],
+                    &labels,
+                ))
+            }
+        })
+    } else {
+        None
+    };
+
+    let sqlite_fns = if cfg!(feature = "sqlite") {
+        let variants_db = backend_values.sqlite;
+        let updates = alias_updates(variants_db, aliases, quote_literal);
+        let values = format!(
+            "\" IN ({}))",
+            literal_list(variants_db, ", ", quote_literal)
+        );
+        Some(quote! {
+            /// A `CHECK` constraint limiting `column` to this enum's values
+            pub fn sqlite_check_constraint_sql(column: &str) -> String {
+                let mut sql = String::from("CHECK (\"");
+                sql.push_str(&column.replace('"', "\"\""));
+                sql.push_str(#values);
+                sql
+            }
+
+            /// The statements updating rows of `table` which still hold an alias in
+            /// `column` to use the value instead
+            pub fn sqlite_update_aliases_sql(table: &str, column: &str) -> String {
+                ::diesel_derive_enum::__private::update_aliases_sql(table, column, '"', &[#(#updates),*])
+            }
+
+            /// Compares the enum's values with a SQLite `CHECK (column IN (...))` constraint
+            pub fn sqlite_verify_schema(
+                conn: &mut diesel::sqlite::SqliteConnection,
+                table: &str,
+                column: &str,
+            ) -> diesel::QueryResult<::diesel_derive_enum::SchemaReport> {
+                use diesel::RunQueryDsl;
+
+                let table_sql = diesel::select(
+                    diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Text>>(
+                        "(SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ",
+                    )
+                    .bind::<diesel::sql_types::Text, _>(table)
+                    .sql(")"),
+                )
+                .get_result::<Option<String>>(conn)?;
+                let labels = table_sql
+                    .as_deref()
+                    .and_then(|sql| ::diesel_derive_enum::__private::parse_sqlite_check(sql, column))
+                    .unwrap_or_default();
+                Ok(::diesel_derive_enum::SchemaReport::new(
+                    #enum_name,
+                    &[#(#variants_db),*],
+                    &[#(#all_aliases),*],
+                    &labels,
+                ))
+            }
+        })
+    } else {
+        None
+    };
+
+    quote! {
+        impl #enum_ty {
+            #pg_fns
+            #mysql_fns
+            #sqlite_fns
+        }
+    }
+}
+
+/// The quoted value and the list of quoted aliases, for each variant with any
+fn alias_updates(
+    variants_db: &[String],
+    aliases: &[Vec<String>],
+    quote: fn(&str) -> String,
+) -> Vec<TokenStream> {
+    variants_db
+        .iter()
+        .zip(aliases)
+        .filter(|(_, aliases)| !aliases.is_empty())
+        .map(|(value, aliases)| {
+            let value = quote(value);
+            let aliases = literal_list(aliases, ", ", quote);
+            quote! { (#value, #aliases) }
+        })
+        .collect()
+}
+
+fn literal_list(values: &[String], separator: &str, quote: fn(&str) -> String) -> String {
+    values
+        .iter()
+        .map(|value| quote(value))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub(crate) fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// MySQL also treats backslashes as escapes within string literals
+pub(crate) fn quote_mysql_literal(value: &str) -> String {
+    quote_literal(&value.replace('\\', "\\\\"))
+}
@@ -0,0 +1,353 @@
+//! Storing the enum as a plain integer column (`#[db_enum(repr = "i16")]`),
+//! rather than as a database enum or text.
+
+use std::collections::HashMap;
+
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Comma;
+use syn::{Error, Expr, ExprLit, ExprUnary, Lit, Result, UnOp, Variant};
+
+use crate::attrs::VariantAttrs;
+use crate::{
+    Errors, Fallback, generate_common_impls, generate_imports, generate_writable_check,
+    mapped_variants, skipped_unreachable_arms, skipped_variants,
+};
+
+/// The Rust integer type, and so the SQL integer type, used to store the enum
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum IntRepr {
+    I16,
+    I32,
+    I64,
+}
+
+impl IntRepr {
+    pub fn from_ident(ident: &Ident) -> Result<Self> {
+        match ident.to_string().as_str() {
+            "i16" => Ok(IntRepr::I16),
+            "i32" => Ok(IntRepr::I32),
+            "i64" => Ok(IntRepr::I64),
+            _ => Err(Error::new(
+                ident.span(),
+                "`repr` must be one of `i16`, `i32`, `i64`",
+            )),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            IntRepr::I16 => "i16",
+            IntRepr::I32 => "i32",
+            IntRepr::I64 => "i64",
+        }
+    }
+
+    fn rust_type(self) -> TokenStream {
+        match self {
+            IntRepr::I16 => quote! { i16 },
+            IntRepr::I32 => quote! { i32 },
+            IntRepr::I64 => quote! { i64 },
+        }
+    }
+
+    fn sql_type(self) -> TokenStream {
+        match self {
+            IntRepr::I16 => quote! { SmallInt },
+            IntRepr::I32 => quote! { Integer },
+            IntRepr::I64 => quote! { BigInt },
+        }
+    }
+
+    fn literal(self, value: i128) -> Option<Literal> {
+        match self {
+            IntRepr::I16 => i16::try_from(value).ok().map(Literal::i16_suffixed),
+            IntRepr::I32 => i32::try_from(value).ok().map(Literal::i32_suffixed),
+            IntRepr::I64 => i64::try_from(value).ok().map(Literal::i64_suffixed),
+        }
+    }
+}
+
+pub(crate) fn generate_integer_impls(
+    repr: IntRepr,
+    enum_ty: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    variant_attrs: &[VariantAttrs],
+) -> Result<TokenStream> {
+    let modname = Ident::new(&format!("db_enum_impl_{}", enum_ty), Span::call_site());
+    let mut errors = Errors::default();
+    let (mapped, fallback) = mapped_variants(variants, variant_attrs, &mut errors);
+    if let Some(Fallback::CatchAll(id)) = &fallback {
+        errors.push(Error::new(
+            id.span(),
+            "A catch-all variant can't be used with `repr`, \
+             mark a unit variant `#[db_enum(other)]` instead",
+        ));
+    }
+
+    // Implicit discriminants follow the same rules as Rust itself, counting
+    // up from the previous variant, even if that one is skipped
+    let mut next_discriminant = Some(0);
+    let mut discriminants = HashMap::new();
+    for variant in variants {
+        let discriminant = match &variant.discriminant {
+            Some((_, expr)) => errors.ok(int_from_expr(expr).map_err(|_| {
+                Error::new_spanned(
+                    expr,
+                    "Only integer literal discriminants can be stored, \
+                     use `#[db_enum(value = ...)]` instead",
+                )
+            })),
+            None => next_discriminant,
+        };
+        next_discriminant = discriminant.map(|discriminant| discriminant + 1);
+        discriminants.insert(&variant.ident, discriminant);
+    }
+    let mut seen: HashMap<i128, &Ident> = HashMap::new();
+    let mut variant_ids = Vec::new();
+    let mut values = Vec::new();
+    let mut expected = Vec::new();
+    for (variant, attrs) in mapped {
+        let id = &variant.ident;
+        if let Some(rename) = attrs.rename.as_ref().or(attrs.backend_rename.any()) {
+            errors.push(Error::new(
+                rename.span(),
+                "`rename` has no effect with `repr`, use `value` instead",
+            ));
+        }
+        if let Some(alias) = attrs.alias.first() {
+            errors.push(Error::new(
+                alias.span(),
+                "`alias` has no effect with `repr`",
+            ));
+        }
+        let discriminant = discriminants[id];
+        let value = match &attrs.value {
+            Some(expr) => errors
+                .ok(int_from_expr(expr))
+                .map(|value| (value, expr.span())),
+            None => discriminant.map(|discriminant| (discriminant, id.span())),
+        };
+        let Some((value, span)) = value else {
+            continue;
+        };
+        let Some(literal) = repr.literal(value) else {
+            errors.push(Error::new(
+                span,
+                format!("`{}` does not fit in `{}`", value, repr.name()),
+            ));
+            continue;
+        };
+        if let Some(first) = seen.get(&value) {
+            errors.push(Error::new(
+                span,
+                format!(
+                    "Variants `{}` and `{}` both map to the database value `{}`",
+                    first, id, value
+                ),
+            ));
+        } else {
+            seen.insert(value, id);
+        }
+        variant_ids.push(quote! {
+            #enum_ty::#id
+        });
+        values.push(literal);
+        expected.push(value.to_string());
+    }
+    errors.finish()?;
+
+    let rust_ty = repr.rust_type();
+    let sql_ty = repr.sql_type();
+    let unrecognized = match fallback {
+        Some(Fallback::Unit(id)) => quote! {
+            _ => Ok(#enum_ty::#id),
+        },
+        _ => {
+            let enum_name = enum_ty.to_string();
+            let sql_type_name = sql_ty.to_string();
+            quote! {
+                v => Err(Box::new(::diesel_derive_enum::UnknownDbEnumValue {
+                    enum_name: #enum_name,
+                    sql_type: #sql_type_name,
+                    value: v.to_string().into_bytes(),
+                    expected: &[#(#expected),*],
+                })),
+            }
+        }
+    };
+    let skipped_arms =
+        skipped_unreachable_arms(enum_ty, &skipped_variants(enum_ty, variants, variant_attrs));
+    let common = quote! {
+        impl #enum_ty {
+            /// Every variant with a database value of its own, in declaration order
+            pub const VARIANTS: &'static [#enum_ty] = &[#(#variant_ids),*];
+
+            /// The database value of each of `VARIANTS`
+            pub const DB_VALUES: &'static [#rust_ty] = &[#(#values),*];
+        }
+
+        fn db_int_representation(e: &#enum_ty) -> #rust_ty {
+            match *e {
+                #(#variant_ids => #values,)*
+                #(#skipped_arms)*
+            }
+        }
+
+        fn from_db_int_representation(value: #rust_ty) -> deserialize::Result<#enum_ty> {
+            match value {
+                #(#values => Ok(#variant_ids),)*
+                #unrecognized
+            }
+        }
+    };
+    let writable_check = generate_writable_check(enum_ty, variants, variant_attrs);
+    let common_impls = generate_common_impls(&sql_ty, enum_ty);
+
+    let pg_impl = if cfg!(feature = "postgres") {
+        Some(generate_raw_bytes_impl(
+            quote! { pg_impl },
+            quote! { diesel::pg::{Pg, PgValue} },
+            quote! { Pg },
+            quote! { PgValue },
+            &sql_ty,
+            &rust_ty,
+            enum_ty,
+        ))
+    } else {
+        None
+    };
+
+    let mysql_impl = if cfg!(feature = "mysql") {
+        Some(generate_raw_bytes_impl(
+            quote! { mysql_impl },
+            quote! { diesel::mysql::{Mysql, MysqlValue} },
+            quote! { Mysql },
+            quote! { MysqlValue },
+            &sql_ty,
+            &rust_ty,
+            enum_ty,
+        ))
+    } else {
+        None
+    };
+
+    let sqlite_impl = if cfg!(feature = "sqlite") {
+        Some(generate_sqlite_impl(repr, &sql_ty, &rust_ty, enum_ty))
+    } else {
+        None
+    };
+
+    let imports = generate_imports();
+
+    Ok(quote! {
+        #[allow(non_snake_case, deprecated)]
+        mod #modname {
+            #imports
+
+            #common
+            #writable_check
+            #common_impls
+            #pg_impl
+            #mysql_impl
+            #sqlite_impl
+        }
+    })
+}
+
+fn int_from_expr(expr: &Expr) -> Result<i128> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_from_expr(expr).map(|value| -value),
+        _ => Err(Error::new_spanned(expr, "expected an integer literal")),
+    }
+}
+
+/// Postgres and MySQL both bind through a byte buffer, so we can hand over
+/// directly to diesel's own integer impls
+fn generate_raw_bytes_impl(
+    modname: TokenStream,
+    backend_imports: TokenStream,
+    backend: TokenStream,
+    raw_value: TokenStream,
+    sql_ty: &TokenStream,
+    rust_ty: &TokenStream,
+    enum_ty: &Ident,
+) -> TokenStream {
+    quote! {
+        mod #modname {
+            use super::*;
+            use #backend_imports;
+
+            impl FromSql<#sql_ty, #backend> for #enum_ty {
+                fn from_sql(raw: #raw_value) -> deserialize::Result<Self> {
+                    from_db_int_representation(<#rust_ty as FromSql<#sql_ty, #backend>>::from_sql(raw)?)
+                }
+            }
+
+            impl ToSql<#sql_ty, #backend> for #enum_ty {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, #backend>) -> serialize::Result {
+                    check_writable(self)?;
+                    ToSql::<#sql_ty, #backend>::to_sql(&db_int_representation(self), &mut out.reborrow())
+                }
+            }
+
+            impl Queryable<#sql_ty, #backend> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
+
+fn generate_sqlite_impl(
+    repr: IntRepr,
+    sql_ty: &TokenStream,
+    rust_ty: &TokenStream,
+    enum_ty: &Ident,
+) -> TokenStream {
+    // sqlite only binds 32 and 64 bit integers
+    let bind_value = match repr {
+        IntRepr::I16 => quote! { i32::from(db_int_representation(self)) },
+        IntRepr::I32 | IntRepr::I64 => quote! { db_int_representation(self) },
+    };
+    quote! {
+        mod sqlite_impl {
+            use super::*;
+            use diesel::sqlite::Sqlite;
+
+            impl FromSql<#sql_ty, Sqlite> for #enum_ty {
+                fn from_sql(value: backend::RawValue<Sqlite>) -> deserialize::Result<Self> {
+                    from_db_int_representation(<#rust_ty as FromSql<#sql_ty, Sqlite>>::from_sql(value)?)
+                }
+            }
+
+            impl ToSql<#sql_ty, Sqlite> for #enum_ty {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+                    check_writable(self)?;
+                    out.set_value(#bind_value);
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl Queryable<#sql_ty, Sqlite> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1575 @@
+#![recursion_limit = "1024"]
+
+extern crate proc_macro;
+
+use heck::{
+    ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase, ToTrainCase,
+    ToUpperCamelCase,
+};
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use std::collections::HashMap;
+use syn::spanned::Spanned;
+use syn::*;
+
+mod attrs;
+mod copy;
+mod ddl;
+mod integer;
+mod order;
+
+use attrs::{Backend, ContainerAttrs, Deprecation, PerBackend, VariantAttrs};
+use copy::{generate_copy_array, generate_copy_array_to_sql};
+use ddl::generate_ddl_fns;
+use integer::generate_integer_impls;
+use order::generate_order_fns;
+
+/// Implement the traits necessary for inserting the enum directly into a database
+///
+/// # Attributes
+///
+/// All options are given through a single `#[db_enum(...)]` attribute, either on
+/// the enum itself or on one of its variants.
+///
+/// ## Type attributes
+///
+/// * `#[db_enum(existing_type_path = crate::schema::sql_types::NewEnum)]` specifies
+///   the path to a corresponding diesel type that was already created by the
+///   diesel CLI. If omitted, the type will be generated by this macro. May be given
+///   more than once to bind the enum to each of the types. The impls are generated
+///   for every enabled backend, so the type must support each of them.
+/// * `#[db_enum(diesel_type = NewEnumMapping)]` specifies the name for the diesel type
+///   to create. If omitted, uses `<enum name>Mapping`.
+///   *Note*: Cannot be specified alongside `existing_type_path`
+/// * `#[db_enum(pg_type = "new_enum")]` specifies the name of the Postgres type.
+///   If omitted, uses the enum name in `snake_case`. Alongside `existing_type_path`,
+///   it only gives the name of that type for the generated SQL, which is otherwise
+///   left out as the name isn't known. Enums only bound to `Text` have no Postgres type.
+/// * `#[db_enum(rename_all = "snake_case")]` specifies a renaming style from each of
+///   the rust enum variants to each of the database variants. Either `camelCase`,
+///   `kebab-case`, `PascalCase`, `SCREAMING_SNAKE_CASE`, `SCREAMING-KEBAB-CASE`,
+///   `UPPERCASE`, `lowercase`, `snake_case`, `Train-Case`, `dot.case` or `verbatim`.
+///   If omitted, uses `snake_case`. The style can also differ between backends, e.g.
+///   `rename_all(mysql = "SCREAMING_SNAKE_CASE")`.
+/// * `#[db_enum(strip_prefix = "...", strip_suffix = "...")]` remove a common prefix or
+///   suffix from the variant names before `rename_all` is applied, e.g. to drop a
+///   `Status` suffix.
+/// * `#[db_enum(prefix = "...", suffix = "...")]` add a prefix or suffix to the database
+///   values after `rename_all` is applied. None of these four affect `rename`d variants.
+/// * `#[db_enum(repr = "i16")]` stores the enum as an integer instead, using each
+///   variant's discriminant. Either `i16`, `i32` or `i64`, which map to the `SmallInt`,
+///   `Integer` and `BigInt` SQL types respectively. No mapping type is generated.
+/// * `#[db_enum(sql_type = diesel::sql_types::Text)]` binds the enum directly to
+///   `Text` (and so also `VarChar`) columns, on every backend. On its own, no mapping
+///   type is generated. Alongside `existing_type_path`, `diesel_type` or `pg_type`,
+///   the enum is bound to both `Text` and those mapping types.
+/// * `#[db_enum(display)]` implements `Display`, writing each variant's database value.
+/// * `#[db_enum(from_str)]` implements `FromStr`, `TryFrom<&str>` and `TryFrom<String>`,
+///   parsing the database values. Unknown values are rejected with an
+///   [`UnknownDbEnumValue`](../diesel_derive_enum/struct.UnknownDbEnumValue.html), unless
+///   there is an `other` variant.
+/// * `#[db_enum(serde)]` implements serde's `Serialize` and `Deserialize`, using the
+///   database values, and decoding unknown values to the `other` variant if there is one.
+///   Requires the `serde` feature.
+/// * `#[db_enum(ord)]` implements `PartialOrd` and `Ord` following the declaration order
+///   of the variants, as Postgres and MySQL order their enums. The enum must also
+///   implement `Eq`. Values of a catch-all `other` variant sort at its position,
+///   compared as text among themselves.
+///
+/// With the `postgres` feature, the generated mapping type also implements diesel's
+/// `SqlOrd`, so columns using it work with `max()`, `min()` and comparisons.
+///
+/// ## Variant attributes
+///
+/// * `#[db_enum(rename = "variant")]` specifies the db name for a specific variant.
+/// * `#[db_enum(rename(postgres = "...", mysql = "...", sqlite = "..."))]` specifies the
+///   db name for some of the backends only, taking precedence over `rename`. The
+///   generated functions and traits that aren't tied to a backend (`DB_VALUES`,
+///   `Display`, serde, ...) always use the values without these overrides.
+/// * `#[db_enum(alias = "old_name")]` accepts another value when reading the variant,
+///   e.g. while a rename is rolled out. Only the variant's own value is ever written. May
+///   be given more than once.
+/// * `#[db_enum(read_only)]` still reads the variant, but makes writing it an error
+///   (`ReadOnlyDbEnumValue`), for values being phased out. Pair it with `#[deprecated]`
+///   to also get a warning wherever it is constructed.
+/// * `#[db_enum(skip)]` leaves a Rust-only variant (which may have fields) out of the
+///   mapping entirely: it is never read, writing it is an error (`SkippedDbEnumVariant`),
+///   and it isn't part of `VARIANTS`, `DB_VALUES` or the schema SQL. `as_db_str` returns
+///   `None` for it, and the enum can't use `display` or implement the `DbEnum` trait.
+/// * `#[db_enum(other)]` marks the variant that any unrecognized database value
+///   is decoded to, rather than returning an error. On a unit variant, the value is
+///   discarded. On a variant with a single `String` field (e.g. `Unknown(String)`),
+///   the raw value is kept and written back unchanged.
+/// * `#[db_enum(value = 7)]` overrides the value stored for a variant when using `repr`.
+///
+/// Variants may be gated behind `#[cfg(...)]`, and any of these behind
+/// `#[cfg_attr(...)]`. They are resolved before the derive runs, so a disabled
+/// variant is left out of every generated conversion, constant and schema SQL. With
+/// `repr`, the variants after a gated one need explicit discriminants, or their stored
+/// integers shift whenever it's disabled.
+///
+/// ## Legacy attributes
+///
+/// The attributes `#[ExistingTypePath = "..."]`, `#[DieselType = "..."]`,
+/// `#[PgType = "..."]`, `#[DbValueStyle = "..."]` and `#[db_rename = "..."]`
+/// are still accepted, but emit a deprecation warning.
+///
+/// # Generated functions
+///
+/// The enum gets a few associated constants describing its values: `VARIANTS` lists the
+/// variants in declaration order, `DB_VALUES` the value each one is stored as, and
+/// `PG_TYPE_NAME` (with the `postgres` feature) the name of the Postgres type, if there
+/// is one and its name is known (see `pg_type`). Unless using `repr`, `as_db_str()`
+/// returns the value a single variant is stored as. A catch-all `other` variant isn't
+/// part of `VARIANTS` or `DB_VALUES`.
+///
+/// Unless using `repr`, the enum also gets associated functions returning the SQL that
+/// declares its values, for each enabled backend: `pg_create_type_sql()` and
+/// `pg_drop_type_sql()` for the Postgres type (whenever `PG_TYPE_NAME` is generated),
+/// `mysql_column_type_sql()` for a MySQL `enum(...)` column, and
+/// `sqlite_check_constraint_sql(column)` for a SQLite `CHECK`.
+///
+/// To finish moving rows off any `alias`, `pg_rename_aliases_sql()` renames each variant's
+/// first alias in the Postgres type, and `pg_update_aliases_sql(table, column)`,
+/// `mysql_update_aliases_sql(table, column)` and `sqlite_update_aliases_sql(table, column)`
+/// update the rows still holding an alias.
+///
+/// Unless using `repr`, `order_by_declaration(column)` wraps a column of the enum in an
+/// expression giving the position of its value in `VARIANTS` (aliases sharing their
+/// variant's), or `NULL` for any other value. It sorts in declaration order on every
+/// backend, e.g. for `Text` columns on SQLite, and can be compared with integers. The
+/// expression's type is named after the enum, e.g. `MyEnumDeclarationOrder<C>`.
+///
+/// With the `postgres` feature and a Postgres type whose name is known (see `pg_type`),
+/// `pg_enum_range()`, `pg_enum_first()` and `pg_enum_last()` call the Postgres functions
+/// of the same names on that type, as typed expressions for use in `select`, `filter`
+/// and the like.
+///
+/// With the `postgres` feature and a Postgres type, `pg_type_metadata(conn)` looks the type
+/// up for binary `COPY FROM`, which can't do so itself when writing arrays of the enum.
+/// Arrays are written by wrapping them in `<enum name>CopyArray::new(&metadata, &values)`.
+///
+/// Likewise, `pg_verify_schema(conn)`, `mysql_verify_schema(conn, table, column)` and
+/// `sqlite_verify_schema(conn, table, column)` compare the enum's values with those the
+/// live database allows, returning a [`SchemaReport`](../diesel_derive_enum/struct.SchemaReport.html).
+/// `pg_verify_schema` needs a Postgres type, so isn't generated for enums only bound to `Text`.
+///
+/// Unless using `repr`, a catch-all `other` variant or `skip` variants, the enum also
+/// implements the [`DbEnum`](../diesel_derive_enum/trait.DbEnum.html) trait, for generic code.
+#[proc_macro_derive(
+    DbEnum,
+    attributes(db_enum, PgType, DieselType, ExistingTypePath, DbValueStyle, db_rename)
+)]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    derive_db_enum(&input).into()
+}
+
+fn derive_db_enum(input: &DeriveInput) -> proc_macro2::TokenStream {
+    let data_variants = match &input.data {
+        Data::Enum(DataEnum { variants, .. }) => variants,
+        Data::Struct(DataStruct { struct_token, .. }) => {
+            return Error::new(
+                struct_token.span,
+                "derive(DbEnum) can only be applied to enums",
+            )
+            .into_compile_error();
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Error::new(
+                union_token.span,
+                "derive(DbEnum) can only be applied to enums",
+            )
+            .into_compile_error();
+        }
+    };
+
+    // Keep going after the first problem so that a single build reports
+    // every invalid attribute at once
+    let mut errors = Errors::default();
+    let attrs = ContainerAttrs::from_attrs(&input.attrs, &mut errors);
+    let variant_attrs: Vec<VariantAttrs> = data_variants
+        .iter()
+        .map(|variant| VariantAttrs::from_attrs(&variant.attrs, &mut errors))
+        .collect();
+
+    // we could allow a default value here but... I'm not very keen
+    // let existing_mapping_path = existing_mapping_path
+    //     .unwrap_or_else(|| format!("crate::schema::sql_types::{}", input.ident));
+
+    if let (Some(_), Some(diesel_type)) = (attrs.existing_type_path.first(), &attrs.diesel_type) {
+        errors.push(Error::new(
+            diesel_type.span(),
+            "Cannot specify both `existing_type_path` and `diesel_type`",
+        ));
+    }
+    let new_diesel_mapping = attrs
+        .diesel_type
+        .clone()
+        .unwrap_or_else(|| Ident::new(&format!("{}Mapping", input.ident), Span::call_site()));
+
+    // Integers are stored in the database's own integer types, so there's no
+    // mapping type or text values to configure
+    if attrs.repr.is_some() {
+        let unused = [
+            (
+                "existing_type_path",
+                attrs.existing_type_path.first().map(Spanned::span),
+            ),
+            ("diesel_type", attrs.diesel_type.as_ref().map(Ident::span)),
+            ("pg_type", attrs.pg_type.as_ref().map(LitStr::span)),
+            (
+                "rename_all",
+                attrs
+                    .rename_all
+                    .or(attrs.backend_rename_all.any().copied())
+                    .map(|(_, span)| span),
+            ),
+            ("prefix", attrs.prefix.as_ref().map(LitStr::span)),
+            ("suffix", attrs.suffix.as_ref().map(LitStr::span)),
+            (
+                "strip_prefix",
+                attrs.strip_prefix.as_ref().map(LitStr::span),
+            ),
+            (
+                "strip_suffix",
+                attrs.strip_suffix.as_ref().map(LitStr::span),
+            ),
+            ("sql_type", attrs.sql_type.as_ref().map(Spanned::span)),
+            ("display", attrs.display),
+            ("from_str", attrs.from_str),
+            ("serde", attrs.serde),
+            ("ord", attrs.ord),
+        ];
+        for (name, span) in unused {
+            if let Some(span) = span {
+                errors.push(Error::new(
+                    span,
+                    format!("`{}` has no effect with `repr`", name),
+                ));
+            }
+        }
+    } else {
+        for value in variant_attrs.iter().filter_map(|v| v.value.as_ref()) {
+            errors.push(Error::new_spanned(value, "`value` requires `repr`"));
+        }
+    }
+
+    // Without any existing types, a mapping type is generated, unless the enum
+    // is only bound to `Text` and none of the generated type's options are given
+    let generate_mapping = attrs.existing_type_path.is_empty()
+        && (attrs.sql_type.is_none() || attrs.diesel_type.is_some() || attrs.pg_type.is_some());
+    let mappings: Vec<Mapping> = generate_mapping
+        .then_some(Mapping::New)
+        .into_iter()
+        .chain(
+            attrs
+                .existing_type_path
+                .iter()
+                .map(|path| Mapping::Existing(quote! { #path })),
+        )
+        .chain(attrs.sql_type.as_ref().map(|_| Mapping::Text))
+        .collect();
+
+    let impls = match attrs.repr {
+        Some((repr, _)) => errors.ok(generate_integer_impls(
+            repr,
+            &input.ident,
+            data_variants,
+            &variant_attrs,
+        )),
+        None => errors.ok(generate_derive_enum_impls(
+            &mappings,
+            &new_diesel_mapping,
+            &attrs,
+            &input.ident,
+            data_variants,
+            &variant_attrs,
+        )),
+    };
+    let output = match errors.finish() {
+        Ok(()) => impls,
+        Err(error) => Some(error.into_compile_error()),
+    };
+    let warnings = attrs
+        .deprecations
+        .iter()
+        .chain(variant_attrs.iter().flat_map(|v| &v.deprecations))
+        .map(Deprecation::warning);
+
+    quote! {
+        #output
+        #(#warnings)*
+    }
+}
+
+/// Accumulates errors so that they can all be reported together
+#[derive(Default)]
+struct Errors(Option<Error>);
+
+impl Errors {
+    fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    fn ok<T>(&mut self, result: Result<T>) -> Option<T> {
+        result.map_err(|error| self.push(error)).ok()
+    }
+
+    fn finish(self) -> Result<()> {
+        match self.0 {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Defines the casing for the database representation.  Follows serde naming convention.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum CaseStyle {
+    Camel,
+    Kebab,
+    Pascal,
+    Upper,
+    Lower,
+    ScreamingSnake,
+    ScreamingKebab,
+    Snake,
+    Train,
+    Dot,
+    Verbatim,
+}
+
+impl CaseStyle {
+    fn from_string(name: &str) -> Option<Self> {
+        match name {
+            "camelCase" => Some(CaseStyle::Camel),
+            "kebab-case" => Some(CaseStyle::Kebab),
+            "PascalCase" => Some(CaseStyle::Pascal),
+            "SCREAMING_SNAKE_CASE" => Some(CaseStyle::ScreamingSnake),
+            "UPPERCASE" => Some(CaseStyle::Upper),
+            "lowercase" => Some(CaseStyle::Lower),
+            "SCREAMING-KEBAB-CASE" => Some(CaseStyle::ScreamingKebab),
+            "snake_case" => Some(CaseStyle::Snake),
+            "Train-Case" => Some(CaseStyle::Train),
+            "dot.case" => Some(CaseStyle::Dot),
+            "verbatim" | "verbatimcase" => Some(CaseStyle::Verbatim),
+            _ => None,
+        }
+    }
+
+    fn from_lit(lit: &LitStr) -> Result<Self> {
+        Self::from_string(&lit.value()).ok_or_else(|| {
+            Error::new(
+                lit.span(),
+                format!(
+                    "unsupported casing: `{}`, expected one of `camelCase`, `kebab-case`, \
+                     `PascalCase`, `SCREAMING_SNAKE_CASE`, `SCREAMING-KEBAB-CASE`, `UPPERCASE`, \
+                     `lowercase`, `snake_case`, `Train-Case`, `dot.case`, `verbatim`",
+                    lit.value()
+                ),
+            )
+        })
+    }
+}
+
+/// A SQL type that the enum's text values are bound to
+enum Mapping {
+    /// A new mapping type, generated by this macro
+    New,
+    /// A mapping type that already exists, usually from `diesel print-schema`
+    Existing(proc_macro2::TokenStream),
+    /// Diesel's own `Text` type, for plain text columns
+    Text,
+}
+
+fn generate_derive_enum_impls(
+    mappings: &[Mapping],
+    new_diesel_mapping: &Ident,
+    attrs: &ContainerAttrs,
+    enum_ty: &Ident,
+    variants: &syn::punctuated::Punctuated<Variant, syn::token::Comma>,
+    variant_attrs: &[VariantAttrs],
+) -> Result<proc_macro2::TokenStream> {
+    let modname = Ident::new(&format!("db_enum_impl_{}", enum_ty), Span::call_site());
+    let pg_internal_type = attrs
+        .pg_type
+        .as_ref()
+        .map(LitStr::value)
+        .unwrap_or_else(|| enum_ty.to_string().to_snake_case());
+    let pg_mapping_index = mappings
+        .iter()
+        .position(|mapping| !matches!(mapping, Mapping::Text));
+    // Text columns have no Postgres type, and an existing type already names its
+    // own, which can't be read from here, so it's only known if given again with
+    // `pg_type`
+    let pg_type_name = (pg_mapping_index.is_some()
+        && (attrs.pg_type.is_some() || attrs.existing_type_path.is_empty()))
+    .then_some(pg_internal_type.as_str());
+
+    let mut errors = Errors::default();
+    let (mapped, fallback) = mapped_variants(variants, variant_attrs, &mut errors);
+    let variant_ids: Vec<proc_macro2::TokenStream> = mapped
+        .iter()
+        .map(|(variant, _)| {
+            let id = &variant.ident;
+            quote! {
+                #enum_ty::#id
+            }
+        })
+        .collect();
+    let variant_idents: Vec<&Ident> = mapped.iter().map(|(variant, _)| &variant.ident).collect();
+
+    let (variants_db, variants_db_spans) = db_values(&mapped, attrs, None);
+    let alias_lits: Vec<&[LitStr]> = mapped
+        .iter()
+        .map(|(_, variant_attrs)| variant_attrs.alias.as_slice())
+        .collect();
+    check_db_values(
+        &variant_idents,
+        &variants_db,
+        &variants_db_spans,
+        &alias_lits,
+        &mut errors,
+    );
+    // Each backend may override some of the values, in which case it gets
+    // its own copy of the conversion functions
+    let backend_values = PerBackend::from_fn(|backend| {
+        let (values, spans) = db_values(&mapped, attrs, Some(backend));
+        if values == variants_db {
+            return None;
+        }
+        check_db_values(&variant_idents, &values, &spans, &alias_lits, &mut errors);
+        Some(values)
+    });
+    let ord_impls = attrs.ord.map(|_| {
+        generate_ord_impls(
+            enum_ty,
+            variants,
+            variant_attrs,
+            fallback.as_ref(),
+            &mut errors,
+        )
+    });
+    if let Some(display) = attrs.display {
+        if variant_attrs.iter().any(|attrs| attrs.skip.is_some()) {
+            errors.push(Error::new(
+                display,
+                "`display` can't write a `skip` variant, which has no database value",
+            ));
+        }
+    }
+    errors.finish()?;
+    let aliases: Vec<Vec<String>> = alias_lits
+        .iter()
+        .map(|lits| lits.iter().map(LitStr::value).collect())
+        .collect();
+    let skipped = skipped_variants(enum_ty, variants, variant_attrs);
+    let backend_common = PerBackend::from_fn(|backend| {
+        backend_values.get(backend).as_ref().map(|values| {
+            generate_common(
+                enum_ty,
+                &variant_ids,
+                values,
+                &aliases,
+                &skipped,
+                fallback.as_ref(),
+            )
+        })
+    });
+
+    let common = generate_common(
+        enum_ty,
+        &variant_ids,
+        &variants_db,
+        &aliases,
+        &skipped,
+        fallback.as_ref(),
+    );
+    let writable_check = generate_writable_check(enum_ty, variants, variant_attrs);
+    let metadata = generate_metadata(
+        enum_ty,
+        &variant_ids,
+        &variants_db,
+        pg_type_name,
+        &skipped,
+        fallback.as_ref(),
+    );
+    let trait_impl = generate_trait_impl(
+        enum_ty,
+        mappings
+            .first()
+            .map(|mapping| diesel_mapping(mapping, new_diesel_mapping)),
+        &skipped,
+        fallback.as_ref(),
+    );
+    let display_impl = attrs.display.map(|_| generate_display_impl(enum_ty));
+    let from_str_impl = attrs.from_str.map(|_| {
+        generate_from_str_impls(
+            enum_ty,
+            &variant_ids,
+            &variants_db,
+            &aliases,
+            fallback.as_ref(),
+            &sql_type_name(&diesel_mapping(&mappings[0], new_diesel_mapping)),
+        )
+    });
+    let serde_impls = attrs.serde.map(|_| {
+        generate_serde_impls(
+            enum_ty,
+            &variant_ids,
+            &variants_db,
+            &aliases,
+            &skipped,
+            fallback.as_ref(),
+        )
+    });
+    let values_per_backend = PerBackend::from_fn(|backend| {
+        backend_values
+            .get(backend)
+            .as_deref()
+            .unwrap_or(&variants_db)
+    });
+    let pg_mapping =
+        pg_mapping_index.map(|index| diesel_mapping(&mappings[index], new_diesel_mapping));
+    let ddl_fns = generate_ddl_fns(
+        enum_ty,
+        pg_type_name,
+        &values_per_backend,
+        &aliases,
+        pg_mapping.as_ref(),
+    );
+    let order_ty = Ident::new(&format!("{}DeclarationOrder", enum_ty), Span::call_site());
+    let order_fns = generate_order_fns(enum_ty, &order_ty, &values_per_backend, &aliases);
+    let copy_ty = Ident::new(&format!("{}CopyArray", enum_ty), Span::call_site());
+    let copy_array = pg_mapping
+        .as_ref()
+        .filter(|_| cfg!(feature = "postgres"))
+        .map(|pg_mapping| generate_copy_array(enum_ty, &copy_ty, pg_mapping));
+    let copy_array_use = copy_array.as_ref().map(|_| {
+        quote! {
+            pub use self::#modname::#copy_ty;
+        }
+    });
+    let (diesel_mapping_def, diesel_mapping_use) = match mappings.first() {
+        Some(Mapping::New) => (
+            Some(generate_new_diesel_mapping(
+                new_diesel_mapping,
+                &pg_internal_type,
+            )),
+            Some(quote! {
+                pub use self::#modname::#new_diesel_mapping;
+            }),
+        ),
+        // Skip this part if we only have existing mappings
+        _ => (None, None),
+    };
+
+    // Each mapping gets its own module, so that the per-backend modules
+    // inside don't clash
+    let mapping_impls = mappings.iter().enumerate().map(|(index, mapping)| {
+        let mapping_modname = Ident::new(&format!("mapping_{}", index), Span::call_site());
+        let diesel_mapping = diesel_mapping(mapping, new_diesel_mapping);
+        let common_impls = generate_common_impls(&diesel_mapping, enum_ty);
+
+        let pg_impl = if cfg!(feature = "postgres") {
+            let copy_array_to_sql = (Some(index) == pg_mapping_index)
+                .then(|| generate_copy_array_to_sql(&copy_ty, &diesel_mapping));
+            Some(generate_postgres_impl(
+                &diesel_mapping,
+                enum_ty,
+                backend_common.postgres.as_ref(),
+                copy_array_to_sql,
+            ))
+        } else {
+            None
+        };
+
+        let mysql_impl = if cfg!(feature = "mysql") {
+            Some(generate_mysql_impl(
+                &diesel_mapping,
+                enum_ty,
+                backend_common.mysql.as_ref(),
+            ))
+        } else {
+            None
+        };
+
+        let sqlite_impl = if cfg!(feature = "sqlite") {
+            Some(generate_sqlite_impl(
+                &diesel_mapping,
+                enum_ty,
+                backend_common.sqlite.as_ref(),
+            ))
+        } else {
+            None
+        };
+
+        quote! {
+            mod #mapping_modname {
+                use super::*;
+
+                #common_impls
+                #pg_impl
+                #mysql_impl
+                #sqlite_impl
+            }
+        }
+    });
+
+    let imports = generate_imports();
+
+    let quoted = quote! {
+        #diesel_mapping_use
+        pub use self::#modname::#order_ty;
+        #copy_array_use
+        // Variants may be `#[deprecated]`, e.g. when they're `read_only`
+        #[allow(non_snake_case, deprecated)]
+        mod #modname {
+            #imports
+
+            #common
+            #writable_check
+            #metadata
+            #trait_impl
+            #display_impl
+            #from_str_impl
+            #ord_impls
+            #serde_impls
+            #ddl_fns
+            #order_fns
+            #copy_array
+            #diesel_mapping_def
+            #(#mapping_impls)*
+        }
+    };
+
+    Ok(quoted)
+}
+
+/// The database value of every variant, along with where it was given, either
+/// for a single backend or for the backend-independent APIs
+fn db_values(
+    mapped: &[(&Variant, &VariantAttrs)],
+    attrs: &ContainerAttrs,
+    backend: Option<Backend>,
+) -> (Vec<String>, Vec<Span>) {
+    let rename_all = backend
+        .and_then(|backend| *attrs.backend_rename_all.get(backend))
+        .or(attrs.rename_all);
+    // Maintain backwards compatibility by defaulting to snake case.
+    let case_style = rename_all.map_or(CaseStyle::Snake, |(style, _)| style);
+    mapped
+        .iter()
+        .map(|(variant, variant_attrs)| {
+            let rename = backend
+                .and_then(|backend| variant_attrs.backend_rename.get(backend).as_ref())
+                .or(variant_attrs.rename.as_ref());
+            match rename {
+                Some(lit) => (lit.value(), lit.span()),
+                None => (
+                    default_db_value(&variant.ident.to_string(), attrs, case_style),
+                    variant.ident.span(),
+                ),
+            }
+        })
+        .unzip()
+}
+
+fn diesel_mapping(mapping: &Mapping, new_diesel_mapping: &Ident) -> proc_macro2::TokenStream {
+    match mapping {
+        Mapping::New => quote! { #new_diesel_mapping },
+        Mapping::Existing(path) => path.clone(),
+        Mapping::Text => quote! { diesel::sql_types::Text },
+    }
+}
+
+fn generate_imports() -> proc_macro2::TokenStream {
+    quote! {
+        use super::*;
+        use diesel::{
+            backend::{self, Backend},
+            deserialize::{self, FromSql},
+            expression::AsExpression,
+            internal::derives::as_expression::Bound,
+            query_builder::{bind_collector::RawBytesBindCollector},
+            row::Row,
+            serialize::{self, IsNull, Output, ToSql},
+            sql_types::*,
+            Queryable,
+        };
+        use std::io::Write;
+    }
+}
+
+/// Every variant needs its own non-empty database value, otherwise decoding
+/// would silently pick the first of several matching variants
+fn check_db_values(
+    variant_idents: &[&Ident],
+    variants_db: &[String],
+    variants_db_spans: &[Span],
+    aliases: &[&[LitStr]],
+    errors: &mut Errors,
+) {
+    let mut seen: HashMap<String, &Ident> = HashMap::new();
+    let values = variant_idents
+        .iter()
+        .zip(variants_db.iter().cloned())
+        .zip(variants_db_spans.iter().copied());
+    let alias_values = variant_idents
+        .iter()
+        .zip(aliases)
+        .flat_map(|(ident, lits)| {
+            lits.iter()
+                .map(move |lit| ((ident, lit.value()), lit.span()))
+        });
+    for ((ident, value), span) in values.chain(alias_values) {
+        if value.is_empty() {
+            errors.push(Error::new(
+                span,
+                format!("Variant `{}` maps to an empty database value", ident),
+            ));
+        } else if let Some(first) = seen.get(value.as_str()) {
+            errors.push(Error::new(
+                span,
+                format!(
+                    "Variants `{}` and `{}` both map to the database value `{}`",
+                    first, ident, value
+                ),
+            ));
+        } else {
+            seen.insert(value, ident);
+        }
+    }
+}
+
+/// Splits off the catch-all variant (if any) and drops the `skip` variants,
+/// leaving the variants which each map to their own database value
+fn mapped_variants<'a>(
+    variants: &'a syn::punctuated::Punctuated<Variant, syn::token::Comma>,
+    variant_attrs: &'a [VariantAttrs],
+    errors: &mut Errors,
+) -> (Vec<(&'a Variant, &'a VariantAttrs)>, Option<Fallback<'a>>) {
+    let mut mapped = Vec::new();
+    let mut fallback = None;
+    for (variant, attrs) in variants.iter().zip(variant_attrs) {
+        let id = &variant.ident;
+        if attrs.skip.is_some() {
+            let conflicting = [
+                (
+                    attrs
+                        .rename
+                        .as_ref()
+                        .or(attrs.backend_rename.any())
+                        .map(LitStr::span),
+                    "rename",
+                ),
+                (attrs.alias.first().map(LitStr::span), "alias"),
+                (attrs.other, "other"),
+                (attrs.read_only, "read_only"),
+                (attrs.value.as_ref().map(Spanned::span), "value"),
+            ];
+            for (span, name) in conflicting {
+                if let Some(span) = span {
+                    errors.push(Error::new(
+                        span,
+                        format!(
+                            "A `skip` variant has no database value, so `{}` has no effect",
+                            name
+                        ),
+                    ));
+                }
+            }
+            continue;
+        }
+        if let Some(other) = attrs.other {
+            if fallback.is_some() {
+                errors.push(Error::new(
+                    other,
+                    "Only one variant can be marked `#[db_enum(other)]`",
+                ));
+            }
+            if let Fields::Unnamed(fields) = &variant.fields {
+                if let Some(rename) = attrs.rename.as_ref().or(attrs.backend_rename.any()) {
+                    errors.push(Error::new(
+                        rename.span(),
+                        "A catch-all variant has no database value of its own to rename",
+                    ));
+                }
+                if let Some(alias) = attrs.alias.first() {
+                    errors.push(Error::new(
+                        alias.span(),
+                        "A catch-all variant already accepts any value, so needs no `alias`",
+                    ));
+                }
+                errors.ok(check_catch_all_fields(fields));
+                fallback = Some(Fallback::CatchAll(id));
+                continue;
+            }
+            fallback = Some(Fallback::Unit(id));
+        }
+        if !matches!(variant.fields, Fields::Unit) {
+            errors.push(Error::new_spanned(
+                &variant.fields,
+                "Variants must be fieldless, unless marked `#[db_enum(other)]` \
+                 with a single `String` field",
+            ));
+        }
+        mapped.push((variant, attrs));
+    }
+    (mapped, fallback)
+}
+
+/// What to decode a database value to when no variant maps to it
+enum Fallback<'a> {
+    /// A unit variant, which is written back as its own database value
+    Unit(&'a Ident),
+    /// A single-field variant holding the raw value, which is written back unchanged
+    CatchAll(&'a Ident),
+}
+
+/// The variants marked `skip` as `match` patterns, along with their names
+pub(crate) fn skipped_variants(
+    enum_ty: &Ident,
+    variants: &syn::punctuated::Punctuated<Variant, syn::token::Comma>,
+    variant_attrs: &[VariantAttrs],
+) -> Vec<(proc_macro2::TokenStream, String)> {
+    variants
+        .iter()
+        .zip(variant_attrs)
+        .filter(|(_, attrs)| attrs.skip.is_some())
+        .map(|(variant, _)| {
+            let id = &variant.ident;
+            (quote! { #enum_ty::#id { .. } }, id.to_string())
+        })
+        .collect()
+}
+
+/// `match` arms for the `skip` variants in the internal conversions, which
+/// are only reached once `check_writable` (or serde) has turned them away
+pub(crate) fn skipped_unreachable_arms(
+    enum_ty: &Ident,
+    skipped: &[(proc_macro2::TokenStream, String)],
+) -> Vec<proc_macro2::TokenStream> {
+    skipped
+        .iter()
+        .map(|(pattern, name)| {
+            let message = format!(
+                "`{}::{}` is marked `skip`, so has no database value",
+                enum_ty, name
+            );
+            quote! { #pattern => unreachable!("{}", #message), }
+        })
+        .collect()
+}
+
+fn check_catch_all_fields(fields: &FieldsUnnamed) -> Result<()> {
+    let is_string = match fields.unnamed.first() {
+        Some(Field {
+            ty: Type::Path(TypePath { path, .. }),
+            ..
+        }) => path
+            .segments
+            .last()
+            .is_some_and(|seg| seg.ident == "String"),
+        _ => false,
+    };
+    if fields.unnamed.len() != 1 || !is_string {
+        return Err(Error::new_spanned(
+            fields,
+            "A catch-all variant must have a single `String` field",
+        ));
+    }
+    Ok(())
+}
+
+fn stylize_value(value: &str, style: CaseStyle) -> String {
+    match style {
+        CaseStyle::Camel => value.to_lower_camel_case(),
+        CaseStyle::Kebab => value.to_kebab_case(),
+        CaseStyle::Pascal => value.to_upper_camel_case(),
+        CaseStyle::Upper => value.to_uppercase(),
+        CaseStyle::Lower => value.to_lowercase(),
+        CaseStyle::ScreamingSnake => value.to_shouty_snake_case(),
+        CaseStyle::ScreamingKebab => value.to_shouty_kebab_case(),
+        CaseStyle::Snake => value.to_snake_case(),
+        CaseStyle::Train => value.to_train_case(),
+        CaseStyle::Dot => value.to_snake_case().replace('_', "."),
+        CaseStyle::Verbatim => value.to_string(),
+    }
+}
+
+/// The database value of a variant without a `rename`: the affixes to strip
+/// are removed from the variant name, then the casing is applied, and only
+/// then are the affixes to add appended
+fn default_db_value(name: &str, attrs: &ContainerAttrs, style: CaseStyle) -> String {
+    let mut name = name.to_string();
+    if let Some(prefix) = &attrs.strip_prefix {
+        if let Some(rest) = name.strip_prefix(prefix.value().as_str()) {
+            name = rest.to_string();
+        }
+    }
+    if let Some(suffix) = &attrs.strip_suffix {
+        if let Some(rest) = name.strip_suffix(suffix.value().as_str()) {
+            name = rest.to_string();
+        }
+    }
+    let mut value = stylize_value(&name, style);
+    if let Some(prefix) = &attrs.prefix {
+        value.insert_str(0, &prefix.value());
+    }
+    if let Some(suffix) = &attrs.suffix {
+        value.push_str(&suffix.value());
+    }
+    value
+}
+
+/// A catch-all variant holds on to its own value, so that can't be `'static`
+fn db_str_type(fallback: Option<&Fallback>) -> proc_macro2::TokenStream {
+    match fallback {
+        Some(Fallback::CatchAll(_)) => quote! { &str },
+        _ => quote! { &'static str },
+    }
+}
+
+/// Exposes the mapping between variants and database values as public API
+fn generate_metadata(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_db: &[String],
+    pg_type_name: Option<&str>,
+    skipped: &[(proc_macro2::TokenStream, String)],
+    fallback: Option<&Fallback>,
+) -> proc_macro2::TokenStream {
+    let str_ty = db_str_type(fallback);
+    // A `skip` variant has no value to return
+    let as_db_str = if skipped.is_empty() {
+        quote! {
+            /// The value this variant is stored as in the database
+            pub fn as_db_str(&self) -> #str_ty {
+                db_str_representation(self)
+            }
+        }
+    } else {
+        let skipped_patterns = skipped.iter().map(|(pattern, _)| pattern);
+        quote! {
+            /// The value this variant is stored as in the database, or `None` for a
+            /// variant marked `skip`
+            pub fn as_db_str(&self) -> Option<#str_ty> {
+                #[allow(unreachable_patterns)]
+                match *self {
+                    #(#skipped_patterns => None,)*
+                    _ => Some(db_str_representation(self)),
+                }
+            }
+        }
+    };
+    let pg_type_name = pg_type_name
+        .filter(|_| cfg!(feature = "postgres"))
+        .map(|pg_type_name| {
+            quote! {
+                /// The name of the enum's Postgres type
+                pub const PG_TYPE_NAME: &'static str = #pg_type_name;
+            }
+        });
+    quote! {
+        impl #enum_ty {
+            /// Every variant with a database value of its own, in declaration order
+            pub const VARIANTS: &'static [#enum_ty] = &[#(#variants_rs),*];
+
+            /// The database value of each of `VARIANTS`
+            pub const DB_VALUES: &'static [&'static str] = &[#(#variants_db),*];
+
+            #pg_type_name
+
+            #as_db_str
+        }
+    }
+}
+
+/// The values of a catch-all variant aren't `'static`, and a `skip` variant
+/// has none, so such enums don't get the trait
+fn generate_trait_impl(
+    enum_ty: &Ident,
+    diesel_mapping: Option<proc_macro2::TokenStream>,
+    skipped: &[(proc_macro2::TokenStream, String)],
+    fallback: Option<&Fallback>,
+) -> Option<proc_macro2::TokenStream> {
+    if let Some(Fallback::CatchAll(_)) = fallback {
+        return None;
+    }
+    if !skipped.is_empty() {
+        return None;
+    }
+    let diesel_mapping = diesel_mapping?;
+    let sql_type_name = sql_type_name(&diesel_mapping);
+    Some(quote! {
+        impl ::diesel_derive_enum::DbEnum for #enum_ty {
+            type Mapping = #diesel_mapping;
+
+            fn db_value(&self) -> &'static str {
+                db_str_representation(self)
+            }
+
+            fn from_db_value(value: &str) -> Option<Self> {
+                from_db_binary_representation(value.as_bytes(), #sql_type_name).ok()
+            }
+
+            fn variants() -> &'static [Self] {
+                Self::VARIANTS
+            }
+        }
+    })
+}
+
+fn generate_display_impl(enum_ty: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        impl std::fmt::Display for #enum_ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.pad(db_str_representation(self))
+            }
+        }
+    }
+}
+
+/// Parses the same strings that are stored in the database, so that they
+/// can't drift apart from the renames
+fn generate_from_str_impls(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_db: &[String],
+    aliases: &[Vec<String>],
+    fallback: Option<&Fallback>,
+    sql_type_name: &str,
+) -> proc_macro2::TokenStream {
+    let (alias_variants, alias_values) = alias_arms(variants_rs, aliases);
+    let unrecognized = match fallback {
+        Some(Fallback::Unit(id)) => quote! {
+            _ => Ok(#enum_ty::#id),
+        },
+        Some(Fallback::CatchAll(id)) => quote! {
+            v => Ok(#enum_ty::#id(v.to_owned())),
+        },
+        None => {
+            let enum_name = enum_ty.to_string();
+            quote! {
+                v => Err(::diesel_derive_enum::UnknownDbEnumValue {
+                    enum_name: #enum_name,
+                    sql_type: #sql_type_name,
+                    value: v.as_bytes().to_vec(),
+                    expected: &[#(#variants_db),*],
+                }),
+            }
+        }
+    };
+    quote! {
+        impl std::str::FromStr for #enum_ty {
+            type Err = ::diesel_derive_enum::UnknownDbEnumValue;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    #(#variants_db => Ok(#variants_rs),)*
+                    #(#alias_values => Ok(#alias_variants),)*
+                    #unrecognized
+                }
+            }
+        }
+
+        impl<'a> std::convert::TryFrom<&'a str> for #enum_ty {
+            type Error = ::diesel_derive_enum::UnknownDbEnumValue;
+
+            fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+                s.parse()
+            }
+        }
+
+        impl std::convert::TryFrom<String> for #enum_ty {
+            type Error = ::diesel_derive_enum::UnknownDbEnumValue;
+
+            fn try_from(s: String) -> Result<Self, Self::Error> {
+                s.parse()
+            }
+        }
+    }
+}
+
+/// Orders the variants as they are declared, which is how Postgres and MySQL
+/// order their enums. Values held by a catch-all variant compare as text.
+fn generate_ord_impls(
+    enum_ty: &Ident,
+    variants: &syn::punctuated::Punctuated<Variant, syn::token::Comma>,
+    variant_attrs: &[VariantAttrs],
+    fallback: Option<&Fallback>,
+    errors: &mut Errors,
+) -> proc_macro2::TokenStream {
+    let index_arms =
+        variants
+            .iter()
+            .zip(variant_attrs)
+            .enumerate()
+            .map(|(index, (variant, attrs))| {
+                let id = &variant.ident;
+                if attrs.skip.is_some() && !matches!(variant.fields, Fields::Unit) {
+                    errors.push(Error::new_spanned(
+                        &variant.fields,
+                        "`ord` can't order the fields of a `skip` variant, \
+                     implement `Ord` yourself instead",
+                    ));
+                }
+                quote! { #enum_ty::#id { .. } => #index, }
+            });
+    let catch_all_cmp = match fallback {
+        Some(Fallback::CatchAll(id)) => Some(quote! {
+            (#enum_ty::#id(a), #enum_ty::#id(b)) => a.cmp(b),
+        }),
+        _ => None,
+    };
+    quote! {
+        fn declaration_index(e: &#enum_ty) -> usize {
+            match *e {
+                #(#index_arms)*
+            }
+        }
+
+        impl std::cmp::Ord for #enum_ty {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                match (self, other) {
+                    #catch_all_cmp
+                    _ => declaration_index(self).cmp(&declaration_index(other)),
+                }
+            }
+        }
+
+        impl std::cmp::PartialOrd for #enum_ty {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+    }
+}
+
+/// Uses exactly the database values, rather than serde's own naming
+fn generate_serde_impls(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_db: &[String],
+    aliases: &[Vec<String>],
+    skipped: &[(proc_macro2::TokenStream, String)],
+    fallback: Option<&Fallback>,
+) -> proc_macro2::TokenStream {
+    let serde = quote! { ::diesel_derive_enum::__private::serde };
+    let enum_name = enum_ty.to_string();
+    let skipped_arms = skipped.iter().map(|(pattern, name)| {
+        quote! {
+            #pattern => Err(#serde::ser::Error::custom(
+                ::diesel_derive_enum::SkippedDbEnumVariant {
+                    enum_name: #enum_name,
+                    variant: #name,
+                },
+            )),
+        }
+    });
+    let (alias_variants, alias_values) = alias_arms(variants_rs, aliases);
+    let unrecognized = match fallback {
+        Some(Fallback::Unit(id)) => quote! {
+            _ => Ok(#enum_ty::#id),
+        },
+        Some(Fallback::CatchAll(id)) => quote! {
+            v => Ok(#enum_ty::#id(v.to_owned())),
+        },
+        None => quote! {
+            v => Err(E::unknown_variant(v, &[#(#variants_db),*])),
+        },
+    };
+    let expecting = format!("a `{}` value", enum_ty);
+    quote! {
+        impl #serde::Serialize for #enum_ty {
+            fn serialize<S: #serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #[allow(unreachable_patterns)]
+                match *self {
+                    #(#skipped_arms)*
+                    _ => serializer.serialize_str(db_str_representation(self)),
+                }
+            }
+        }
+
+        impl<'de> #serde::Deserialize<'de> for #enum_ty {
+            fn deserialize<D: #serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct DbValueVisitor;
+
+                impl<'de> #serde::de::Visitor<'de> for DbValueVisitor {
+                    type Value = #enum_ty;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.write_str(#expecting)
+                    }
+
+                    fn visit_str<E: #serde::de::Error>(self, v: &str) -> Result<#enum_ty, E> {
+                        match v {
+                            #(#variants_db => Ok(#variants_rs),)*
+                            #(#alias_values => Ok(#alias_variants),)*
+                            #unrecognized
+                        }
+                    }
+                }
+
+                deserializer.deserialize_str(DbValueVisitor)
+            }
+        }
+    }
+}
+
+fn generate_common(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_db: &[String],
+    aliases: &[Vec<String>],
+    skipped: &[(proc_macro2::TokenStream, String)],
+    fallback: Option<&Fallback>,
+) -> proc_macro2::TokenStream {
+    let skipped_arms = skipped_unreachable_arms(enum_ty, skipped);
+    let variants_db_bytes = variants_db
+        .iter()
+        .map(|variant_str| LitByteStr::new(variant_str.as_bytes(), Span::call_site()));
+    let (alias_variants, alias_values) = alias_arms(variants_rs, aliases);
+    let alias_bytes = alias_values
+        .iter()
+        .map(|alias| LitByteStr::new(alias.as_bytes(), Span::call_site()));
+    let str_ty = db_str_type(fallback);
+    let catch_all_str = match fallback {
+        Some(Fallback::CatchAll(id)) => Some(quote! {
+            #enum_ty::#id(ref value) => value.as_str(),
+        }),
+        _ => None,
+    };
+    let unrecognized = match fallback {
+        Some(Fallback::Unit(id)) => quote! {
+            _ => Ok(#enum_ty::#id),
+        },
+        Some(Fallback::CatchAll(id)) => quote! {
+            v => Ok(#enum_ty::#id(String::from_utf8(v.to_vec())?)),
+        },
+        None => {
+            let enum_name = enum_ty.to_string();
+            quote! {
+                v => Err(Box::new(::diesel_derive_enum::UnknownDbEnumValue {
+                    enum_name: #enum_name,
+                    sql_type,
+                    value: v.to_vec(),
+                    expected: &[#(#variants_db),*],
+                })),
+            }
+        }
+    };
+    quote! {
+        fn db_str_representation(e: &#enum_ty) -> #str_ty {
+            match *e {
+                #(#variants_rs => #variants_db,)*
+                #catch_all_str
+                #(#skipped_arms)*
+            }
+        }
+
+        #[allow(dead_code, unused_variables)]
+        fn from_db_binary_representation(
+            bytes: &[u8],
+            sql_type: &'static str,
+        ) -> deserialize::Result<#enum_ty> {
+            match bytes {
+                #(#variants_db_bytes => Ok(#variants_rs),)*
+                #(#alias_bytes => Ok(#alias_variants),)*
+                #unrecognized
+            }
+        }
+    }
+}
+
+/// Refuses to write any variant marked `read_only`, which may only be read, or
+/// `skip`, which has no database value at all
+pub(crate) fn generate_writable_check(
+    enum_ty: &Ident,
+    variants: &syn::punctuated::Punctuated<Variant, syn::token::Comma>,
+    variant_attrs: &[VariantAttrs],
+) -> proc_macro2::TokenStream {
+    let enum_name = enum_ty.to_string();
+    let skipped = skipped_variants(enum_ty, variants, variant_attrs)
+        .into_iter()
+        .map(|(pattern, name)| {
+            quote! {
+                #pattern => Err(Box::new(::diesel_derive_enum::SkippedDbEnumVariant {
+                    enum_name: #enum_name,
+                    variant: #name,
+                })),
+            }
+        });
+    let refused: Vec<_> = variants
+        .iter()
+        .zip(variant_attrs)
+        .filter(|(_, attrs)| attrs.read_only.is_some())
+        .map(|(variant, _)| {
+            let id = &variant.ident;
+            let name = id.to_string();
+            let pattern = match variant.fields {
+                Fields::Unit => quote! { #enum_ty::#id },
+                _ => quote! { #enum_ty::#id(..) },
+            };
+            quote! {
+                #pattern => Err(Box::new(::diesel_derive_enum::ReadOnlyDbEnumValue {
+                    enum_name: #enum_name,
+                    variant: #name,
+                })),
+            }
+        })
+        .chain(skipped)
+        .collect();
+    let body = if refused.is_empty() {
+        quote! { Ok(()) }
+    } else {
+        quote! {
+            #[allow(unreachable_patterns)]
+            match *e {
+                #(#refused)*
+                _ => Ok(()),
+            }
+        }
+    };
+    quote! {
+        #[allow(unused_variables)]
+        fn check_writable(
+            e: &#enum_ty,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            #body
+        }
+    }
+}
+
+/// Pairs each alias with the variant it is read as, for use in `match` arms
+fn alias_arms<'a>(
+    variants_rs: &'a [proc_macro2::TokenStream],
+    aliases: &'a [Vec<String>],
+) -> (Vec<&'a proc_macro2::TokenStream>, Vec<&'a String>) {
+    variants_rs
+        .iter()
+        .zip(aliases)
+        .flat_map(|(variant, aliases)| aliases.iter().map(move |alias| (variant, alias)))
+        .unzip()
+}
+
+fn generate_new_diesel_mapping(
+    new_diesel_mapping: &Ident,
+    pg_internal_type: &str,
+) -> proc_macro2::TokenStream {
+    // Postgres orders enum values as they are declared, so `max()`, `min()`
+    // and comparisons are meaningful there
+    let sql_ord = if cfg!(feature = "postgres") {
+        Some(quote! {
+            impl diesel::sql_types::SqlOrd for #new_diesel_mapping {}
+        })
+    } else {
+        None
+    };
+    // Note - we only generate a new mapping for mysql and sqlite, postgres
+    // should already have one
+    quote! {
+        #[derive(Clone, SqlType, diesel::query_builder::QueryId)]
+        #[diesel(mysql_type(name = "Enum"))]
+        #[diesel(sqlite_type(name = "Text"))]
+        #[diesel(postgres_type(name = #pg_internal_type))]
+        pub struct #new_diesel_mapping;
+
+        #sql_ord
+    }
+}
+
+fn generate_common_impls(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+) -> proc_macro2::TokenStream {
+    quote! {
+        impl AsExpression<#diesel_mapping> for #enum_ty {
+            type Expression = Bound<#diesel_mapping, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl AsExpression<Nullable<#diesel_mapping>> for #enum_ty {
+            type Expression = Bound<Nullable<#diesel_mapping>, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a> AsExpression<#diesel_mapping> for &'a #enum_ty {
+            type Expression = Bound<#diesel_mapping, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a> AsExpression<Nullable<#diesel_mapping>> for &'a #enum_ty {
+            type Expression = Bound<Nullable<#diesel_mapping>, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a, 'b> AsExpression<#diesel_mapping> for &'a &'b #enum_ty {
+            type Expression = Bound<#diesel_mapping, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a, 'b> AsExpression<Nullable<#diesel_mapping>> for &'a &'b #enum_ty {
+            type Expression = Bound<Nullable<#diesel_mapping>, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<DB> ToSql<Nullable<#diesel_mapping>, DB> for #enum_ty
+        where
+            DB: Backend,
+            Self: ToSql<#diesel_mapping, DB>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+                ToSql::<#diesel_mapping, DB>::to_sql(self, out)
+            }
+        }
+    }
+}
+
+/// The mapping type as written, for error messages
+fn sql_type_name(diesel_mapping: &proc_macro2::TokenStream) -> String {
+    diesel_mapping.to_string().replace(' ', "")
+}
+
+fn generate_postgres_impl(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+    value_fns: Option<&proc_macro2::TokenStream>,
+    copy_array_to_sql: Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let sql_type_name = sql_type_name(diesel_mapping);
+    quote! {
+        mod pg_impl {
+            use super::*;
+            #value_fns
+            use diesel::pg::{Pg, PgValue};
+
+            impl FromSql<#diesel_mapping, Pg> for #enum_ty {
+                fn from_sql(raw: PgValue) -> deserialize::Result<Self> {
+                    from_db_binary_representation(raw.as_bytes(), #sql_type_name)
+                }
+            }
+
+            impl ToSql<#diesel_mapping, Pg> for #enum_ty
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                    check_writable(self)?;
+                    out.write_all(db_str_representation(self).as_bytes())?;
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl Queryable<#diesel_mapping, Pg> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+
+            #copy_array_to_sql
+        }
+    }
+}
+
+fn generate_mysql_impl(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+    value_fns: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let sql_type_name = sql_type_name(diesel_mapping);
+    quote! {
+        mod mysql_impl {
+            use super::*;
+            #value_fns
+            use diesel;
+            use diesel::mysql::{Mysql, MysqlValue};
+
+            impl FromSql<#diesel_mapping, Mysql> for #enum_ty {
+                fn from_sql(raw: MysqlValue) -> deserialize::Result<Self> {
+                    from_db_binary_representation(raw.as_bytes(), #sql_type_name)
+                }
+            }
+
+            impl ToSql<#diesel_mapping, Mysql> for #enum_ty
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> serialize::Result {
+                    check_writable(self)?;
+                    out.write_all(db_str_representation(self).as_bytes())?;
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl Queryable<#diesel_mapping, Mysql> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
+
+fn generate_sqlite_impl(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+    value_fns: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let sql_type_name = sql_type_name(diesel_mapping);
+    quote! {
+        mod sqlite_impl {
+            use super::*;
+            #value_fns
+            use diesel;
+            use diesel::sql_types;
+            use diesel::sqlite::Sqlite;
+
+            impl FromSql<#diesel_mapping, Sqlite> for #enum_ty {
+                fn from_sql(value: backend::RawValue<Sqlite>) -> deserialize::Result<Self> {
+                    let bytes = <Vec<u8> as FromSql<sql_types::Binary, Sqlite>>::from_sql(value)?;
+                    from_db_binary_representation(bytes.as_slice(), #sql_type_name)
+                }
+            }
+
+            impl ToSql<#diesel_mapping, Sqlite> for #enum_ty {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+                    check_writable(self)?;
+                    <str as ToSql<sql_types::Text, Sqlite>>::to_sql(db_str_representation(self), out)
+                }
+            }
+
+            impl Queryable<#diesel_mapping, Sqlite> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! An SQL expression sorting by the declaration order of the variants, for the
+//! backends which don't already order the column that way (e.g. SQLite `Text`).
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+use crate::attrs::PerBackend;
+use crate::ddl::{quote_literal, quote_mysql_literal};
+
+pub(crate) fn generate_order_fns(
+    enum_ty: &Ident,
+    order_ty: &Ident,
+    backend_values: &PerBackend<&[String]>,
+    aliases: &[Vec<String>],
+) -> TokenStream {
+    // Postgres casts each `WHEN` literal to the column's type, which fails for an
+    // enum type lacking any of the values or aliases, so they're compared as text
+    let pg_impl = if cfg!(feature = "postgres") {
+        let (_, after) = case_sql(backend_values.postgres, aliases, quote_literal);
+        Some(query_fragment(
+            order_ty,
+            quote! { diesel::pg::Pg },
+            "(CASE (",
+            &format!(")::text{}", after),
+        ))
+    } else {
+        None
+    };
+
+    // `FIELD()` is 1-based and returns 0 for values it doesn't know, so it is
+    // shifted to agree with `CASE`. It can't give an alias the same position as
+    // its variant though.
+    let mysql_impl = if cfg!(feature = "mysql") {
+        let values = backend_values.mysql;
+        let (before, after) = if aliases.iter().all(Vec::is_empty) {
+            let labels = values
+                .iter()
+                .map(|value| quote_mysql_literal(value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            (
+                "(NULLIF(FIELD(".to_string(),
+                format!(", {}), 0) - 1)", labels),
+            )
+        } else {
+            case_sql(values, aliases, quote_mysql_literal)
+        };
+        Some(query_fragment(
+            order_ty,
+            quote! { diesel::mysql::Mysql },
+            &before,
+            &after,
+        ))
+    } else {
+        None
+    };
+
+    let sqlite_impl = if cfg!(feature = "sqlite") {
+        let (before, after) = case_sql(backend_values.sqlite, aliases, quote_literal);
+        Some(query_fragment(
+            order_ty,
+            quote! { diesel::sqlite::Sqlite },
+            &before,
+            &after,
+        ))
+    } else {
+        None
+    };
+
+    quote! {
+        /// The position of a column's value among the variants, see
+        /// `order_by_declaration`
+        #[derive(Debug, Clone, Copy, diesel::query_builder::QueryId)]
+        pub struct #order_ty<C>(C);
+
+        impl<C: diesel::Expression> diesel::Expression for #order_ty<C> {
+            type SqlType = Nullable<Integer>;
+        }
+
+        impl<C, QS> diesel::AppearsOnTable<QS> for #order_ty<C>
+        where
+            C: diesel::AppearsOnTable<QS>,
+            Self: diesel::Expression,
+        {
+        }
+
+        impl<C, QS> diesel::SelectableExpression<QS> for #order_ty<C>
+        where
+            C: diesel::SelectableExpression<QS>,
+            Self: diesel::AppearsOnTable<QS>,
+        {
+        }
+
+        impl<C, GB> diesel::expression::ValidGrouping<GB> for #order_ty<C>
+        where
+            C: diesel::expression::ValidGrouping<GB>,
+        {
+            type IsAggregate = C::IsAggregate;
+        }
+
+        #pg_impl
+        #mysql_impl
+        #sqlite_impl
+
+        impl #enum_ty {
+            /// The position of `column`'s value in `VARIANTS`, or `NULL` for any other
+            /// value. Unlike the column itself, this sorts in declaration order on
+            /// every backend, e.g. `.order_by(MyEnum::order_by_declaration(my_enum))`.
+            pub fn order_by_declaration<C>(column: C) -> #order_ty<C>
+            where
+                C: diesel::Expression,
+                C::SqlType: SqlType,
+                #enum_ty: AsExpression<C::SqlType>,
+            {
+                #order_ty(column)
+            }
+        }
+    }
+}
+
+fn query_fragment(
+    order_ty: &Ident,
+    backend: TokenStream,
+    before: &str,
+    after: &str,
+) -> TokenStream {
+    quote! {
+        impl<C> diesel::query_builder::QueryFragment<#backend> for #order_ty<C>
+        where
+            C: diesel::query_builder::QueryFragment<#backend>,
+        {
+            fn walk_ast<'b>(
+                &'b self,
+                mut out: diesel::query_builder::AstPass<'_, 'b, #backend>,
+            ) -> diesel::QueryResult<()> {
+                out.push_sql(#before);
+                self.0.walk_ast(out.reborrow())?;
+                out.push_sql(#after);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// `CASE column WHEN 'foo' THEN 0 ... END`, split around the column. Aliases
+/// share the position of their variant.
+fn case_sql(
+    values: &[String],
+    aliases: &[Vec<String>],
+    quote: fn(&str) -> String,
+) -> (String, String) {
+    let mut after = String::new();
+    for (index, (value, aliases)) in values.iter().zip(aliases).enumerate() {
+        for value in std::iter::once(value).chain(aliases) {
+            after.push_str(&format!(" WHEN {} THEN {}", quote(value), index));
+        }
+    }
+    after.push_str(" END)");
+    ("(CASE ".to_string(), after)
+}
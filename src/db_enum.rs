@@ -0,0 +1,30 @@
+//! A trait over derived enums, for code that works with any of them.
+
+/// Implemented by `#[derive(DbEnum)]`, so that generic code (repositories, admin
+/// endpoints, tests and the like) can work with any derived enum
+///
+/// ```ignore
+/// fn options<E: diesel_derive_enum::DbEnum>() -> Vec<&'static str> {
+///     E::variants().iter().map(|v| v.db_value()).collect()
+/// }
+/// ```
+///
+/// Enums with a catch-all `other` variant or `skip` variants, or stored as
+/// integers using `repr`, don't implement this: their database values aren't
+/// all known strings.
+pub trait DbEnum: Sized + 'static {
+    /// The diesel SQL type the enum is mapped to. If it is bound to several, this
+    /// is the first of them: the generated mapping type, the first
+    /// `existing_type_path`, or else `Text`.
+    type Mapping;
+
+    /// The value this variant is stored as in the database
+    fn db_value(&self) -> &'static str;
+
+    /// The variant stored as `value` in the database, if there is one. With an
+    /// `other` variant, that is returned for any unknown value.
+    fn from_db_value(value: &str) -> Option<Self>;
+
+    /// Every variant with a database value of its own, in declaration order
+    fn variants() -> &'static [Self];
+}
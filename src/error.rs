@@ -0,0 +1,112 @@
+//! The errors returned when the database holds a value the enum doesn't know,
+//! or when a read-only variant is written.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+/// A value read from the database doesn't match any variant of the enum
+///
+/// Every generated `FromSql` impl returns this. Diesel wraps it in errors of its
+/// own, so use [`UnknownDbEnumValue::find`] to recover it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownDbEnumValue {
+    /// The name of the Rust enum
+    pub enum_name: &'static str,
+    /// The diesel SQL type the value was read as
+    pub sql_type: &'static str,
+    /// The raw value, as read from the database
+    pub value: Vec<u8>,
+    /// The values the enum does know
+    pub expected: &'static [&'static str],
+}
+
+impl UnknownDbEnumValue {
+    /// Looks for this error within `error` or any of its sources
+    pub fn find<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a Self> {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(unknown) = error.downcast_ref::<Self>() {
+                return Some(unknown);
+            }
+            current = error.source();
+        }
+        None
+    }
+
+    /// The raw value as text, replacing any invalid UTF-8
+    pub fn value_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.value)
+    }
+}
+
+impl fmt::Display for UnknownDbEnumValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unrecognized enum variant: '{}' for `{}` ({}), expected one of {:?}",
+            self.value_lossy(),
+            self.enum_name,
+            self.sql_type,
+            self.expected
+        )
+    }
+}
+
+impl Error for UnknownDbEnumValue {}
+
+/// A variant marked `#[db_enum(read_only)]` was about to be written to the database
+///
+/// Every generated `ToSql` impl returns this for such variants, which diesel
+/// reports as `Error::SerializationError`:
+///
+/// ```ignore
+/// if let Err(diesel::result::Error::SerializationError(err)) = result {
+///     if let Some(read_only) = err.downcast_ref::<ReadOnlyDbEnumValue>() {
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadOnlyDbEnumValue {
+    /// The name of the Rust enum
+    pub enum_name: &'static str,
+    /// The name of the read-only variant
+    pub variant: &'static str,
+}
+
+impl fmt::Display for ReadOnlyDbEnumValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}::{}` is read-only, so can't be written to the database",
+            self.enum_name, self.variant
+        )
+    }
+}
+
+impl Error for ReadOnlyDbEnumValue {}
+
+/// A variant marked `#[db_enum(skip)]` was about to be written to the database
+///
+/// Such variants only exist in Rust, so the generated `ToSql` impls return
+/// this for them, reported by diesel as `Error::SerializationError`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedDbEnumVariant {
+    /// The name of the Rust enum
+    pub enum_name: &'static str,
+    /// The name of the skipped variant
+    pub variant: &'static str,
+}
+
+impl fmt::Display for SkippedDbEnumVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}::{}` is marked `skip`, so has no database value",
+            self.enum_name, self.variant
+        )
+    }
+}
+
+impl Error for SkippedDbEnumVariant {}
@@ -0,0 +1,36 @@
+//! Statements moving rows off aliased values, for the generated
+//! `*_update_aliases_sql` functions.
+
+/// Builds one `UPDATE` per variant, from its quoted value and the list of its
+/// quoted aliases, quoting `table` and `column` with `identifier_quote`
+pub fn update_aliases_sql(
+    table: &str,
+    column: &str,
+    identifier_quote: char,
+    updates: &[(&str, &str)],
+) -> String {
+    let table = quote_identifier(table, identifier_quote);
+    let column = quote_identifier(column, identifier_quote);
+    updates
+        .iter()
+        .map(|(value, aliases)| {
+            format!(
+                "UPDATE {} SET {} = {} WHERE {} IN ({})",
+                table, column, value, column, aliases
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(";\n")
+}
+
+fn quote_identifier(name: &str, quote: char) -> String {
+    let mut quoted = String::from(quote);
+    for c in name.chars() {
+        if c == quote {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push(quote);
+    quoted
+}
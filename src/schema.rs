@@ -0,0 +1,214 @@
+//! Comparing an enum's values with the labels that the live database allows,
+//! for the generated `*_verify_schema` functions.
+
+use std::error::Error;
+use std::fmt;
+
+/// How an enum's database values differ from the labels that the database
+/// itself allows, as returned by the generated `*_verify_schema` functions
+///
+/// If the type or constraint couldn't be found at all, every value of the enum
+/// is reported as missing from the database.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaReport {
+    /// The name of the Rust enum
+    pub enum_name: &'static str,
+    /// Values the enum writes, but which the database would reject
+    pub missing_in_database: Vec<String>,
+    /// Values the database allows, but which the enum can't read
+    pub missing_in_enum: Vec<String>,
+    /// Whether the values known to both are declared in a different order
+    pub order_differs: bool,
+}
+
+impl SchemaReport {
+    /// Aliases are still read by the enum, so aren't reported as missing from it
+    #[doc(hidden)]
+    pub fn new(
+        enum_name: &'static str,
+        expected: &[&str],
+        aliases: &[&str],
+        actual: &[String],
+    ) -> Self {
+        let missing_in_database = expected
+            .iter()
+            .filter(|value| !actual.iter().any(|label| label == *value))
+            .map(|value| value.to_string())
+            .collect();
+        let missing_in_enum = actual
+            .iter()
+            .filter(|label| {
+                !expected.contains(&label.as_str()) && !aliases.contains(&label.as_str())
+            })
+            .cloned()
+            .collect();
+        let shared_expected = expected
+            .iter()
+            .filter(|value| actual.iter().any(|label| label == *value));
+        let shared_actual = actual
+            .iter()
+            .filter(|label| expected.contains(&label.as_str()));
+        let order_differs = !shared_expected.eq(shared_actual);
+        SchemaReport {
+            enum_name,
+            missing_in_database,
+            missing_in_enum,
+            order_differs,
+        }
+    }
+
+    /// Whether the enum and the database agree exactly
+    pub fn is_consistent(&self) -> bool {
+        self.missing_in_database.is_empty()
+            && self.missing_in_enum.is_empty()
+            && !self.order_differs
+    }
+}
+
+impl fmt::Display for SchemaReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_consistent() {
+            return write!(f, "`{}` matches the database", self.enum_name);
+        }
+        write!(f, "`{}` doesn't match the database:", self.enum_name)?;
+        if !self.missing_in_database.is_empty() {
+            write!(
+                f,
+                " missing in the database: {:?};",
+                self.missing_in_database
+            )?;
+        }
+        if !self.missing_in_enum.is_empty() {
+            write!(f, " missing in the enum: {:?};", self.missing_in_enum)?;
+        }
+        if self.order_differs {
+            write!(f, " values are declared in a different order;")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for SchemaReport {}
+
+/// Reads the labels from a MySQL column type, e.g. `enum('foo','bar')`
+pub fn parse_mysql_enum(column_type: &str) -> Option<Vec<String>> {
+    let tokens = tokenize(column_type, true)?;
+    match tokens.as_slice() {
+        [Token::Ident(name), rest @ ..] if name.eq_ignore_ascii_case("enum") => {
+            let (labels, rest) = parse_list(rest)?;
+            rest.is_empty().then_some(labels)
+        }
+        _ => None,
+    }
+}
+
+/// Reads the labels from a `CHECK (column IN (...))` constraint within a
+/// SQLite `CREATE TABLE` statement
+pub fn parse_sqlite_check(table_sql: &str, column: &str) -> Option<Vec<String>> {
+    let tokens = tokenize(table_sql, false)?;
+    let mut rest = tokens.as_slice();
+    while let Some((token, tail)) = rest.split_first() {
+        rest = tail;
+        if !matches!(token, Token::Ident(name) if name.eq_ignore_ascii_case("check")) {
+            continue;
+        }
+        if let [
+            Token::Punct('('),
+            Token::Ident(name),
+            Token::Ident(keyword),
+            list @ ..,
+        ] = tail
+        {
+            if name.eq_ignore_ascii_case(column) && keyword.eq_ignore_ascii_case("in") {
+                if let Some((labels, [Token::Punct(')'), ..])) = parse_list(list) {
+                    return Some(labels);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    /// A bare or quoted identifier, or keyword
+    Ident(String),
+    Str(String),
+    Punct(char),
+}
+
+/// Splits SQL into just enough tokens to find lists of string literals
+fn tokenize(sql: &str, backslash_escapes: bool) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '\'' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next()? {
+                        '\'' if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            value.push('\'');
+                        }
+                        '\'' => break,
+                        '\\' if backslash_escapes => value.push(chars.next()?),
+                        c => value.push(c),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                let mut name = String::new();
+                loop {
+                    match chars.next()? {
+                        c if c == close && chars.peek() == Some(&close) && close != ']' => {
+                            chars.next();
+                            name.push(c);
+                        }
+                        c if c == close => break,
+                        c => name.push(c),
+                    }
+                }
+                tokens.push(Token::Ident(name));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut name = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '$') {
+                        break;
+                    }
+                    name.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(name));
+            }
+            c => tokens.push(Token::Punct(c)),
+        }
+    }
+    Some(tokens)
+}
+
+/// Parses `('a', 'b', ...)`, returning the values and the remaining tokens
+fn parse_list(tokens: &[Token]) -> Option<(Vec<String>, &[Token])> {
+    let [Token::Punct('('), rest @ ..] = tokens else {
+        return None;
+    };
+    let mut rest = rest;
+    let mut values = Vec::new();
+    loop {
+        match rest {
+            [Token::Str(value), Token::Punct(','), tail @ ..] => {
+                values.push(value.clone());
+                rest = tail;
+            }
+            [Token::Str(value), Token::Punct(')'), tail @ ..] => {
+                values.push(value.clone());
+                return Some((values, tail));
+            }
+            _ => return None,
+        }
+    }
+}
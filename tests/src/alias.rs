@@ -0,0 +1,168 @@
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "alias_stage", from_str, serde)]
+pub enum Stage {
+    Draft,
+    #[db_enum(alias = "in_review", alias = "pending")]
+    Review,
+    Published,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::StageMapping;
+    test_alias {
+        id -> Integer,
+        stage -> StageMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_alias)]
+struct Article {
+    id: i32,
+    stage: Stage,
+}
+
+#[cfg(feature = "postgres")]
+fn create_table(conn: &mut PgConnection) {
+    // During the migration the type holds the old labels as well as the new
+    conn.batch_execute(
+        "CREATE TYPE alias_stage AS ENUM ('draft', 'in_review', 'pending', 'review', 'published');
+         CREATE TABLE test_alias (id SERIAL PRIMARY KEY, stage alias_stage NOT NULL);",
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+fn create_table(conn: &mut MysqlConnection) {
+    conn.batch_execute(
+        "CREATE TEMPORARY TABLE IF NOT EXISTS test_alias (id SERIAL PRIMARY KEY, \
+         stage enum('draft', 'in_review', 'pending', 'review', 'published') NOT NULL)",
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+fn create_table(conn: &mut SqliteConnection) {
+    conn.batch_execute("CREATE TABLE test_alias (id SERIAL PRIMARY KEY, stage TEXT NOT NULL)")
+        .unwrap();
+}
+
+#[test]
+fn aliases_are_read_but_not_written() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    connection
+        .batch_execute(
+            "INSERT INTO test_alias (id, stage) VALUES (1, 'in_review'), (2, 'pending'), (3, 'draft')",
+        )
+        .unwrap();
+    let items = test_alias::table
+        .order(test_alias::id)
+        .load::<Article>(connection)
+        .unwrap();
+    let stages: Vec<Stage> = items.into_iter().map(|item| item.stage).collect();
+    assert_eq!(stages, [Stage::Review, Stage::Review, Stage::Draft]);
+
+    diesel::update(test_alias::table.find(1))
+        .set(test_alias::stage.eq(Stage::Review))
+        .execute(connection)
+        .unwrap();
+    let stored = diesel::dsl::sql::<diesel::sql_types::Text>(
+        "SELECT CAST(stage AS TEXT) FROM test_alias ORDER BY id",
+    )
+    .load::<String>(connection)
+    .unwrap();
+    assert_eq!(stored, ["review", "pending", "draft"]);
+}
+
+#[test]
+fn aliases_are_parsed() {
+    assert_eq!("pending".parse(), Ok(Stage::Review));
+    assert_eq!(
+        serde_json::from_str::<Stage>(r#""in_review""#).unwrap(),
+        Stage::Review
+    );
+    assert_eq!(
+        serde_json::to_string(&Stage::Review).unwrap(),
+        r#""review""#
+    );
+    assert_eq!(Stage::DB_VALUES, &["draft", "review", "published"]);
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn pg_alias_sql() {
+    assert_eq!(
+        Stage::pg_rename_aliases_sql(),
+        r#"ALTER TYPE "alias_stage" RENAME VALUE 'in_review' TO 'review'"#
+    );
+    assert_eq!(
+        Stage::pg_update_aliases_sql("test_alias", "stage"),
+        r#"UPDATE "test_alias" SET "stage" = 'review' WHERE "stage" IN ('in_review', 'pending')"#
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn pg_rename_aliases() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TYPE alias_stage AS ENUM ('draft', 'in_review', 'published')")
+        .unwrap();
+    let report = Stage::pg_verify_schema(connection).unwrap();
+    assert_eq!(report.missing_in_database, ["review"]);
+    // Aliases are still understood, so aren't missing from the enum
+    assert!(report.missing_in_enum.is_empty());
+    connection
+        .batch_execute(Stage::pg_rename_aliases_sql())
+        .unwrap();
+    assert!(Stage::pg_verify_schema(connection).unwrap().is_consistent());
+}
+
+#[cfg(feature = "mysql")]
+#[test]
+fn mysql_alias_sql() {
+    assert_eq!(
+        Stage::mysql_update_aliases_sql("test_alias", "stage"),
+        "UPDATE `test_alias` SET `stage` = 'review' WHERE `stage` IN ('in_review', 'pending')"
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_alias_sql() {
+    assert_eq!(
+        Stage::sqlite_update_aliases_sql("test_alias", "stage"),
+        r#"UPDATE "test_alias" SET "stage" = 'review' WHERE "stage" IN ('in_review', 'pending')"#
+    );
+}
+
+#[test]
+fn update_aliases() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    connection
+        .batch_execute(
+            "INSERT INTO test_alias (id, stage) VALUES (1, 'in_review'), (2, 'pending'), (3, 'draft')",
+        )
+        .unwrap();
+    #[cfg(feature = "postgres")]
+    let sql = Stage::pg_update_aliases_sql("test_alias", "stage");
+    #[cfg(feature = "mysql")]
+    let sql = Stage::mysql_update_aliases_sql("test_alias", "stage");
+    #[cfg(feature = "sqlite")]
+    let sql = Stage::sqlite_update_aliases_sql("test_alias", "stage");
+    connection.batch_execute(&sql).unwrap();
+    let stored = diesel::dsl::sql::<diesel::sql_types::Text>(
+        "SELECT CAST(stage AS TEXT) FROM test_alias ORDER BY id",
+    )
+    .load::<String>(connection)
+    .unwrap();
+    assert_eq!(stored, ["review", "review", "draft"]);
+}
@@ -0,0 +1,130 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(rename_all(mysql = "SCREAMING_SNAKE_CASE"))]
+pub enum TaskState {
+    Todo,
+    #[db_enum(rename(
+        postgres = "in_progress",
+        mysql = "IN_PROGRESS",
+        sqlite = "in-progress"
+    ))]
+    InProgress,
+    #[db_enum(rename = "finished", rename(sqlite = "done"))]
+    Done,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::TaskStateMapping;
+    test_backend_rename {
+        id -> Integer,
+        state -> TaskStateMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_backend_rename)]
+struct Task {
+    id: i32,
+    state: TaskState,
+}
+
+#[cfg(feature = "postgres")]
+const EXPECTED: [&str; 3] = ["todo", "in_progress", "finished"];
+#[cfg(feature = "mysql")]
+const EXPECTED: [&str; 3] = ["TODO", "IN_PROGRESS", "finished"];
+#[cfg(feature = "sqlite")]
+const EXPECTED: [&str; 3] = ["todo", "in-progress", "done"];
+
+#[cfg(feature = "postgres")]
+fn create_table(conn: &mut PgConnection) {
+    conn.batch_execute(TaskState::pg_create_type_sql()).unwrap();
+    conn.batch_execute(
+        "CREATE TABLE test_backend_rename (id SERIAL PRIMARY KEY, state task_state NOT NULL)",
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+fn create_table(conn: &mut MysqlConnection) {
+    conn.batch_execute(&format!(
+        "CREATE TEMPORARY TABLE IF NOT EXISTS test_backend_rename \
+         (id SERIAL PRIMARY KEY, state {} NOT NULL)",
+        TaskState::mysql_column_type_sql()
+    ))
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+fn create_table(conn: &mut SqliteConnection) {
+    conn.batch_execute(&format!(
+        "CREATE TABLE test_backend_rename (id SERIAL PRIMARY KEY, state TEXT NOT NULL {})",
+        TaskState::sqlite_check_constraint_sql("state")
+    ))
+    .unwrap();
+}
+
+#[test]
+fn backend_values_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Task {
+            id: 1,
+            state: TaskState::Todo,
+        },
+        Task {
+            id: 2,
+            state: TaskState::InProgress,
+        },
+        Task {
+            id: 3,
+            state: TaskState::Done,
+        },
+    ];
+    insert_into(test_backend_rename::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_backend_rename::table
+        .order(test_backend_rename::id)
+        .load::<Task>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    let stored = diesel::dsl::sql::<diesel::sql_types::Text>(
+        "SELECT CAST(state AS TEXT) FROM test_backend_rename ORDER BY id",
+    )
+    .load::<String>(connection)
+    .unwrap();
+    assert_eq!(stored, EXPECTED);
+}
+
+#[test]
+fn backend_independent_values() {
+    // Everything not tied to a backend uses the values without overrides
+    assert_eq!(TaskState::DB_VALUES, &["todo", "in_progress", "finished"]);
+    assert_eq!(TaskState::Done.as_db_str(), "finished");
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn backend_unknown_value() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            "CREATE TABLE test_backend_rename (id SERIAL PRIMARY KEY, state TEXT NOT NULL);
+             INSERT INTO test_backend_rename (id, state) VALUES (1, 'finished');",
+        )
+        .unwrap();
+    let err = test_backend_rename::table
+        .load::<Task>(connection)
+        .unwrap_err();
+    let unknown = diesel_derive_enum::UnknownDbEnumValue::find(&err).unwrap();
+    assert_eq!(unknown.value_lossy(), "finished");
+    assert_eq!(unknown.expected, EXPECTED);
+}
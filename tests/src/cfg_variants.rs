@@ -0,0 +1,135 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+// Run the tests both with and without the `beta` feature
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "cfg_release")]
+pub enum Release {
+    Stable,
+    #[cfg(feature = "beta")]
+    Beta,
+    #[cfg_attr(feature = "beta", db_enum(rename = "nightly_beta"))]
+    Nightly,
+}
+
+// Explicit discriminants, as implicit ones after `Beta` would shift with the feature
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(repr = "i16")]
+pub enum Channel {
+    Stable = 0,
+    #[cfg(feature = "beta")]
+    Beta = 1,
+    Nightly = 2,
+}
+
+table! {
+    use diesel::sql_types::{Integer, SmallInt};
+    use super::ReleaseMapping;
+    test_cfg_variants {
+        id -> Integer,
+        release -> ReleaseMapping,
+        channel -> SmallInt,
+    }
+}
+
+#[derive(Insertable, Queryable, Debug, PartialEq)]
+#[diesel(table_name = test_cfg_variants)]
+struct Build {
+    id: i32,
+    release: Release,
+    channel: Channel,
+}
+
+#[cfg(feature = "postgres")]
+fn create_table(conn: &mut PgConnection) {
+    conn.batch_execute(Release::pg_create_type_sql()).unwrap();
+    conn.batch_execute(
+        "CREATE TEMPORARY TABLE test_cfg_variants (
+            id SERIAL PRIMARY KEY,
+            release cfg_release NOT NULL,
+            channel SMALLINT NOT NULL
+        )",
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+fn create_table(conn: &mut MysqlConnection) {
+    conn.batch_execute(&format!(
+        "CREATE TEMPORARY TABLE IF NOT EXISTS test_cfg_variants (
+            id SERIAL PRIMARY KEY,
+            release {} NOT NULL,
+            channel SMALLINT NOT NULL
+        )",
+        Release::mysql_column_type_sql()
+    ))
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+fn create_table(conn: &mut SqliteConnection) {
+    conn.batch_execute(&format!(
+        "CREATE TEMPORARY TABLE test_cfg_variants (
+            id INTEGER PRIMARY KEY,
+            release TEXT NOT NULL {},
+            channel SMALLINT NOT NULL
+        )",
+        Release::sqlite_check_constraint_sql("release")
+    ))
+    .unwrap();
+}
+
+#[cfg(feature = "beta")]
+#[test]
+fn enabled_variants_are_mapped() {
+    assert_eq!(Release::DB_VALUES, &["stable", "beta", "nightly_beta"]);
+    assert_eq!(Channel::DB_VALUES, &[0, 1, 2]);
+}
+
+#[cfg(not(feature = "beta"))]
+#[test]
+fn disabled_variants_are_left_out() {
+    assert_eq!(Release::DB_VALUES, &["stable", "nightly"]);
+    assert_eq!(Channel::DB_VALUES, &[0, 2]);
+    #[cfg(feature = "sqlite")]
+    assert_eq!(
+        Release::sqlite_check_constraint_sql("release"),
+        r#"CHECK ("release" IN ('stable', 'nightly'))"#
+    );
+}
+
+#[test]
+fn cfg_variants_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Build {
+            id: 1,
+            release: Release::Stable,
+            channel: Channel::Stable,
+        },
+        #[cfg(feature = "beta")]
+        Build {
+            id: 2,
+            release: Release::Beta,
+            channel: Channel::Beta,
+        },
+        Build {
+            id: 3,
+            release: Release::Nightly,
+            channel: Channel::Nightly,
+        },
+    ];
+    insert_into(test_cfg_variants::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let builds = test_cfg_variants::table
+        .order(test_cfg_variants::id)
+        .load::<Build>(connection)
+        .unwrap();
+    assert_eq!(builds, data);
+}
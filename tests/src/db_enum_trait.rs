@@ -0,0 +1,51 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(rename_all = "kebab-case")]
+pub enum Season {
+    EarlySpring,
+    Summer,
+    #[db_enum(rename = "fall")]
+    Autumn,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(sql_type = diesel::sql_types::Text)]
+pub enum Weather {
+    Sunny,
+    #[db_enum(other)]
+    Other,
+}
+
+fn db_values<E: DbEnum>() -> Vec<&'static str> {
+    E::variants().iter().map(DbEnum::db_value).collect()
+}
+
+fn round_trips<E: DbEnum + PartialEq + std::fmt::Debug>() {
+    for variant in E::variants() {
+        assert_eq!(E::from_db_value(variant.db_value()).as_ref(), Some(variant));
+    }
+}
+
+fn mapped_to<E: DbEnum<Mapping = M>, M>() {}
+
+#[test]
+fn generic_over_db_enum() {
+    assert_eq!(db_values::<Season>(), ["early-spring", "summer", "fall"]);
+    assert_eq!(db_values::<Weather>(), ["sunny", "other"]);
+    round_trips::<Season>();
+    round_trips::<Weather>();
+}
+
+#[test]
+fn from_db_value() {
+    assert_eq!(Season::from_db_value("fall"), Some(Season::Autumn));
+    assert_eq!(Season::from_db_value("Autumn"), None);
+    assert_eq!(Weather::from_db_value("hail"), Some(Weather::Other));
+}
+
+#[test]
+fn mapping_type() {
+    mapped_to::<Season, SeasonMapping>();
+    mapped_to::<Weather, diesel::sql_types::Text>();
+}
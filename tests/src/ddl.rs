@@ -0,0 +1,134 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "Ddl_Mood")]
+pub enum Mood {
+    Happy,
+    #[db_enum(rename = "it's fine")]
+    Fine,
+    #[db_enum(rename = "back\\slash")]
+    Backslash,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::MoodMapping;
+    test_ddl {
+        id -> Integer,
+        mood -> MoodMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_ddl)]
+struct Feeling {
+    id: i32,
+    mood: Mood,
+}
+
+fn sample_data() -> Vec<Feeling> {
+    vec![
+        Feeling {
+            id: 1,
+            mood: Mood::Happy,
+        },
+        Feeling {
+            id: 2,
+            mood: Mood::Fine,
+        },
+        Feeling {
+            id: 3,
+            mood: Mood::Backslash,
+        },
+    ]
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    conn.batch_execute(Mood::pg_create_type_sql()).unwrap();
+    conn.batch_execute(r#"CREATE TABLE test_ddl (id SERIAL PRIMARY KEY, mood "Ddl_Mood" NOT NULL)"#)
+        .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    conn.batch_execute(&format!(
+        "CREATE TEMPORARY TABLE IF NOT EXISTS test_ddl (id SERIAL PRIMARY KEY, mood {} NOT NULL)",
+        Mood::mysql_column_type_sql()
+    ))
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    conn.batch_execute(&format!(
+        "CREATE TABLE test_ddl (id SERIAL PRIMARY KEY, mood TEXT NOT NULL {})",
+        Mood::sqlite_check_constraint_sql("mood")
+    ))
+    .unwrap();
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn pg_type_sql() {
+    assert_eq!(
+        Mood::pg_create_type_sql(),
+        r#"CREATE TYPE "Ddl_Mood" AS ENUM ('happy', 'it''s fine', 'back\slash')"#
+    );
+    assert_eq!(Mood::pg_drop_type_sql(), r#"DROP TYPE "Ddl_Mood""#);
+}
+
+#[cfg(feature = "mysql")]
+#[test]
+fn mysql_column_type_sql() {
+    assert_eq!(
+        Mood::mysql_column_type_sql(),
+        r#"enum('happy','it''s fine','back\\slash')"#
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_check_constraint_sql() {
+    assert_eq!(
+        Mood::sqlite_check_constraint_sql("mood"),
+        r#"CHECK ("mood" IN ('happy', 'it''s fine', 'back\slash'))"#
+    );
+    assert_eq!(
+        Mood::sqlite_check_constraint_sql(r#"odd "column""#),
+        r#"CHECK ("odd ""column""" IN ('happy', 'it''s fine', 'back\slash'))"#
+    );
+}
+
+#[test]
+fn ddl_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = sample_data();
+    insert_into(test_ddl::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_ddl::table
+        .order(test_ddl::id)
+        .load::<Feeling>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    // The database rejects anything that isn't one of the enum's values
+    assert!(connection
+        .batch_execute("INSERT INTO test_ddl (id, mood) VALUES (4, 'sad')")
+        .is_err());
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn pg_drop_type() {
+    let connection = &mut get_connection();
+    connection.batch_execute(Mood::pg_create_type_sql()).unwrap();
+    connection.batch_execute(Mood::pg_drop_type_sql()).unwrap();
+    connection.batch_execute(Mood::pg_create_type_sql()).unwrap();
+}
@@ -0,0 +1,183 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Fruit {
+    Apple,
+    Banana,
+    #[db_enum(other)]
+    Other,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Vegetable {
+    Carrot,
+    #[db_enum(other)]
+    Unknown(String),
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::FruitMapping;
+    test_fruit {
+        id -> Integer,
+        value -> FruitMapping,
+    }
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::VegetableMapping;
+    test_vegetable {
+        id -> Integer,
+        value -> VegetableMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_fruit)]
+struct FruitRow {
+    id: i32,
+    value: Fruit,
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_vegetable)]
+struct VegetableRow {
+    id: i32,
+    value: Vegetable,
+}
+
+// The database knows about labels that the Rust enums don't ('cherry', 'potato')
+
+#[cfg(feature = "postgres")]
+pub fn create_tables(conn: &mut PgConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TYPE fruit AS ENUM ('apple', 'banana', 'other', 'cherry');
+        CREATE TABLE test_fruit (
+            id SERIAL PRIMARY KEY,
+            value fruit NOT NULL
+        );
+        CREATE TYPE vegetable AS ENUM ('carrot', 'potato');
+        CREATE TABLE test_vegetable (
+            id SERIAL PRIMARY KEY,
+            value vegetable NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_tables(conn: &mut MysqlConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_fruit (
+            id SERIAL PRIMARY KEY,
+            value enum('apple', 'banana', 'other', 'cherry') NOT NULL
+        );
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_vegetable (
+            id SERIAL PRIMARY KEY,
+            value enum('carrot', 'potato') NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_tables(conn: &mut SqliteConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_fruit (
+            id SERIAL PRIMARY KEY,
+            value TEXT CHECK(value IN ('apple', 'banana', 'other', 'cherry')) NOT NULL
+        );
+        CREATE TABLE test_vegetable (
+            id SERIAL PRIMARY KEY,
+            value TEXT CHECK(value IN ('carrot', 'potato')) NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn unknown_value_decodes_to_other() {
+    let connection = &mut get_connection();
+    create_tables(connection);
+    connection
+        .batch_execute(
+            "INSERT INTO test_fruit (id, value) VALUES (1, 'apple'), (2, 'cherry'), (3, 'other');",
+        )
+        .unwrap();
+    let items = test_fruit::table
+        .order(test_fruit::id)
+        .load::<FruitRow>(connection)
+        .unwrap();
+    assert_eq!(
+        items,
+        vec![
+            FruitRow {
+                id: 1,
+                value: Fruit::Apple
+            },
+            FruitRow {
+                id: 2,
+                value: Fruit::Other
+            },
+            FruitRow {
+                id: 3,
+                value: Fruit::Other
+            },
+        ]
+    );
+}
+
+#[test]
+fn catch_all_round_trip() {
+    let connection = &mut get_connection();
+    create_tables(connection);
+    connection
+        .batch_execute("INSERT INTO test_vegetable (id, value) VALUES (1, 'potato');")
+        .unwrap();
+    let data = vec![
+        VegetableRow {
+            id: 2,
+            value: Vegetable::Carrot,
+        },
+        VegetableRow {
+            id: 3,
+            value: Vegetable::Unknown("potato".to_string()),
+        },
+    ];
+    insert_into(test_vegetable::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_vegetable::table
+        .order(test_vegetable::id)
+        .load::<VegetableRow>(connection)
+        .unwrap();
+    assert_eq!(
+        items,
+        vec![
+            VegetableRow {
+                id: 1,
+                value: Vegetable::Unknown("potato".to_string()),
+            },
+            VegetableRow {
+                id: 2,
+                value: Vegetable::Carrot,
+            },
+            VegetableRow {
+                id: 3,
+                value: Vegetable::Unknown("potato".to_string()),
+            },
+        ]
+    );
+}
@@ -0,0 +1,178 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+use diesel_derive_enum::UnknownDbEnumValue;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(repr = "i16")]
+pub enum Priority {
+    Low = 1,
+    Medium,
+    High = 10,
+    #[db_enum(value = -1)]
+    Unset,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(repr = i64)]
+pub enum Status {
+    Active,
+    Inactive,
+    #[db_enum(other)]
+    Unknown,
+}
+
+table! {
+    use diesel::sql_types::{BigInt, Integer, Nullable, SmallInt};
+    test_integer_repr {
+        id -> Integer,
+        priority -> SmallInt,
+        maybe_priority -> Nullable<SmallInt>,
+        status -> BigInt,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_integer_repr)]
+struct IntegerRepr {
+    id: i32,
+    priority: Priority,
+    maybe_priority: Option<Priority>,
+    status: Status,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_integer_repr (
+            id SERIAL PRIMARY KEY,
+            priority SMALLINT NOT NULL,
+            maybe_priority SMALLINT,
+            status BIGINT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_integer_repr (
+            id SERIAL PRIMARY KEY,
+            priority SMALLINT NOT NULL,
+            maybe_priority SMALLINT,
+            status BIGINT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_integer_repr (
+            id SERIAL PRIMARY KEY,
+            priority SMALLINT NOT NULL,
+            maybe_priority SMALLINT,
+            status BIGINT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+fn sample_data() -> Vec<IntegerRepr> {
+    vec![
+        IntegerRepr {
+            id: 1,
+            priority: Priority::Low,
+            maybe_priority: None,
+            status: Status::Active,
+        },
+        IntegerRepr {
+            id: 2,
+            priority: Priority::Medium,
+            maybe_priority: Some(Priority::High),
+            status: Status::Inactive,
+        },
+        IntegerRepr {
+            id: 3,
+            priority: Priority::High,
+            maybe_priority: Some(Priority::Unset),
+            status: Status::Unknown,
+        },
+        IntegerRepr {
+            id: 4,
+            priority: Priority::Unset,
+            maybe_priority: None,
+            status: Status::Active,
+        },
+    ]
+}
+
+#[test]
+fn integer_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = sample_data();
+    insert_into(test_integer_repr::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_integer_repr::table
+        .order(test_integer_repr::id)
+        .load::<IntegerRepr>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+
+    let stored = test_integer_repr::table
+        .select((test_integer_repr::priority, test_integer_repr::status))
+        .order(test_integer_repr::id)
+        .load::<(i16, i64)>(connection)
+        .unwrap();
+    assert_eq!(stored, vec![(1, 0), (2, 1), (10, 2), (-1, 0)]);
+
+    let high = test_integer_repr::table
+        .filter(test_integer_repr::priority.eq(Priority::High))
+        .select(test_integer_repr::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(high, vec![3]);
+}
+
+#[test]
+fn integer_unknown_values() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    connection
+        .batch_execute(
+            "INSERT INTO test_integer_repr (id, priority, maybe_priority, status) \
+             VALUES (1, 2, NULL, 42);",
+        )
+        .unwrap();
+    let status = test_integer_repr::table
+        .select(test_integer_repr::status)
+        .first::<Status>(connection)
+        .unwrap();
+    assert_eq!(status, Status::Unknown);
+
+    connection
+        .batch_execute("UPDATE test_integer_repr SET priority = 3;")
+        .unwrap();
+    let err = test_integer_repr::table
+        .select(test_integer_repr::priority)
+        .first::<Priority>(connection)
+        .unwrap_err();
+    let unknown = UnknownDbEnumValue::find(&err).unwrap();
+    assert_eq!(unknown.enum_name, "Priority");
+    assert_eq!(unknown.sql_type, "SmallInt");
+    assert_eq!(unknown.value_lossy(), "3");
+    assert_eq!(unknown.expected, &["1", "2", "10", "-1"]);
+}
@@ -0,0 +1,135 @@
+// The top-level attributes predate `#[db_enum(...)]`. They are deprecated,
+// but must keep working.
+#![allow(deprecated)]
+
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DieselType = "Legacy_Internal_Type"]
+#[PgType = "Legacy_External_Type"]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum LegacyEnum {
+    FirstVariant,
+    #[db_rename = "second"]
+    SecondVariant,
+}
+
+#[cfg(feature = "postgres")]
+#[derive(diesel::sql_types::SqlType)]
+#[diesel(postgres_type(name = "legacy_remote_enum"))]
+pub struct LegacyRemoteEnumMapping;
+
+#[cfg(feature = "postgres")]
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath = "LegacyRemoteEnumMapping"]
+pub enum LegacyRemoteEnum {
+    This,
+    That,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::Legacy_Internal_Type;
+    test_legacy {
+        id -> Integer,
+        value -> Legacy_Internal_Type,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_legacy)]
+struct TestLegacy {
+    id: i32,
+    value: LegacyEnum,
+}
+
+fn sample_data() -> Vec<TestLegacy> {
+    vec![
+        TestLegacy {
+            id: 1,
+            value: LegacyEnum::FirstVariant,
+        },
+        TestLegacy {
+            id: 2,
+            value: LegacyEnum::SecondVariant,
+        },
+    ]
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn legacy_round_trip() {
+    use diesel::connection::SimpleConnection;
+    use diesel::insert_into;
+    let data = sample_data();
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TYPE "Legacy_External_Type" AS ENUM ('FIRST_VARIANT', 'second');
+        CREATE TABLE test_legacy (
+            id SERIAL PRIMARY KEY,
+            value "Legacy_External_Type" NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    let inserted = insert_into(test_legacy::table)
+        .values(&data)
+        .get_results(connection)
+        .unwrap();
+    assert_eq!(data, inserted);
+}
+
+#[test]
+#[cfg(feature = "mysql")]
+fn legacy_round_trip() {
+    use diesel::connection::SimpleConnection;
+    use diesel::insert_into;
+    let data = sample_data();
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_legacy (
+            id SERIAL PRIMARY KEY,
+            value enum('FIRST_VARIANT', 'second') NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    insert_into(test_legacy::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let inserted = test_legacy::table.load::<TestLegacy>(connection).unwrap();
+    assert_eq!(data, inserted);
+}
+
+#[test]
+#[cfg(feature = "sqlite")]
+fn legacy_round_trip() {
+    use diesel::connection::SimpleConnection;
+    use diesel::insert_into;
+    let data = sample_data();
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TABLE test_legacy (
+            id SERIAL PRIMARY KEY,
+            value TEXT CHECK(value IN ('FIRST_VARIANT', 'second')) NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    insert_into(test_legacy::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let inserted = test_legacy::table.load::<TestLegacy>(connection).unwrap();
+    assert_eq!(data, inserted);
+}
@@ -3,12 +3,37 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod alias;
+mod backend_rename;
+mod cfg_variants;
 mod common;
 mod complex_join;
+mod db_enum_trait;
+mod ddl;
+mod fallback;
+mod integer_repr;
+mod legacy_attributes;
+mod metadata;
+mod multiple_mappings;
 mod nullable;
+mod ordering;
 #[cfg(feature = "postgres")]
 mod pg_array;
 #[cfg(feature = "postgres")]
+mod pg_copy;
+#[cfg(feature = "postgres")]
+mod pg_enum_functions;
+#[cfg(feature = "postgres")]
+mod pg_existing_type;
+#[cfg(feature = "postgres")]
 mod pg_remote_type;
+mod read_only;
+mod serde;
+mod shared_schema;
 mod simple;
+mod skip;
+mod string_conversions;
+mod text_column;
+mod unknown_value;
 mod value_style;
+mod verify_schema;
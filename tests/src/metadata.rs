@@ -0,0 +1,61 @@
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "traffic_light", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TrafficLight {
+    Red,
+    #[db_enum(rename = "yellow")]
+    Amber,
+    FlashingGreen,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Tag {
+    Known,
+    #[db_enum(other)]
+    Unknown(String),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(repr = "i32")]
+pub enum Level {
+    Low = 1,
+    High = 5,
+}
+
+#[test]
+fn variant_metadata() {
+    assert_eq!(
+        TrafficLight::VARIANTS,
+        &[
+            TrafficLight::Red,
+            TrafficLight::Amber,
+            TrafficLight::FlashingGreen
+        ]
+    );
+    assert_eq!(
+        TrafficLight::DB_VALUES,
+        &["RED", "yellow", "FLASHING_GREEN"]
+    );
+    for (variant, value) in TrafficLight::VARIANTS.iter().zip(TrafficLight::DB_VALUES) {
+        assert_eq!(variant.as_db_str(), *value);
+    }
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn pg_type_name() {
+    assert_eq!(TrafficLight::PG_TYPE_NAME, "traffic_light");
+    assert_eq!(Tag::PG_TYPE_NAME, "tag");
+}
+
+#[test]
+fn catch_all_metadata() {
+    assert_eq!(Tag::VARIANTS, &[Tag::Known]);
+    assert_eq!(Tag::DB_VALUES, &["known"]);
+    assert_eq!(Tag::Unknown("mystery".to_string()).as_db_str(), "mystery");
+}
+
+#[test]
+fn integer_metadata() {
+    assert_eq!(Level::VARIANTS, &[Level::Low, Level::High]);
+    assert_eq!(Level::DB_VALUES, &[1, 5]);
+}
@@ -0,0 +1,217 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+// Stored as a database enum in one table, and as plain text in another
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(diesel_type = ShapeMapping, sql_type = diesel::sql_types::Text)]
+pub enum Shape {
+    Circle,
+    Square,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable, Text};
+    use super::ShapeMapping;
+    test_shapes {
+        id -> Integer,
+        shape -> ShapeMapping,
+        shape_text -> Text,
+        maybe_shape_text -> Nullable<Text>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_shapes)]
+struct ShapeRow {
+    id: i32,
+    shape: Shape,
+    shape_text: Shape,
+    maybe_shape_text: Option<Shape>,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TYPE shape AS ENUM ('circle', 'square');
+        CREATE TABLE test_shapes (
+            id SERIAL PRIMARY KEY,
+            shape shape NOT NULL,
+            shape_text TEXT NOT NULL,
+            maybe_shape_text TEXT
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_shapes (
+            id SERIAL PRIMARY KEY,
+            shape enum('circle', 'square') NOT NULL,
+            shape_text TEXT NOT NULL,
+            maybe_shape_text TEXT
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_shapes (
+            id SERIAL PRIMARY KEY,
+            shape TEXT CHECK(shape IN ('circle', 'square')) NOT NULL,
+            shape_text TEXT NOT NULL,
+            maybe_shape_text TEXT
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn generated_and_text_mappings_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        ShapeRow {
+            id: 1,
+            shape: Shape::Circle,
+            shape_text: Shape::Square,
+            maybe_shape_text: None,
+        },
+        ShapeRow {
+            id: 2,
+            shape: Shape::Square,
+            shape_text: Shape::Circle,
+            maybe_shape_text: Some(Shape::Square),
+        },
+    ];
+    insert_into(test_shapes::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_shapes::table
+        .filter(test_shapes::shape.eq(Shape::Square))
+        .or_filter(test_shapes::shape_text.eq(Shape::Square))
+        .order(test_shapes::id)
+        .load::<ShapeRow>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+}
+
+// Several existing Postgres types, plus text, along with their array forms
+
+#[cfg(feature = "postgres")]
+#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+#[diesel(postgres_type(name = "status"))]
+pub struct StatusType;
+
+#[cfg(feature = "postgres")]
+#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+#[diesel(postgres_type(name = "legacy_status"))]
+pub struct LegacyStatusType;
+
+#[cfg(feature = "postgres")]
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(
+    existing_type_path = StatusType,
+    existing_type_path = LegacyStatusType,
+    sql_type = diesel::sql_types::Text
+)]
+pub enum Status {
+    Active,
+    Retired,
+}
+
+#[cfg(feature = "postgres")]
+table! {
+    use diesel::sql_types::{Array, Integer, Nullable, Text};
+    use super::{LegacyStatusType, StatusType};
+    test_statuses {
+        id -> Integer,
+        status -> StatusType,
+        legacy_status -> Nullable<LegacyStatusType>,
+        audit -> Text,
+        history -> Array<StatusType>,
+        audit_history -> Nullable<Array<Text>>,
+    }
+}
+
+#[cfg(feature = "postgres")]
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_statuses)]
+struct StatusRow {
+    id: i32,
+    status: Status,
+    legacy_status: Option<Status>,
+    audit: Status,
+    history: Vec<Status>,
+    audit_history: Option<Vec<Status>>,
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn existing_and_text_mappings_round_trip() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+            CREATE TYPE status AS ENUM ('active', 'retired');
+            CREATE TYPE legacy_status AS ENUM ('active', 'retired', 'archived');
+            CREATE TABLE test_statuses (
+                id SERIAL PRIMARY KEY,
+                status status NOT NULL,
+                legacy_status legacy_status,
+                audit TEXT NOT NULL,
+                history status[] NOT NULL,
+                audit_history TEXT[]
+            );
+        "#,
+        )
+        .unwrap();
+    let data = vec![
+        StatusRow {
+            id: 1,
+            status: Status::Active,
+            legacy_status: Some(Status::Retired),
+            audit: Status::Active,
+            history: vec![Status::Retired, Status::Active],
+            audit_history: Some(vec![Status::Active]),
+        },
+        StatusRow {
+            id: 2,
+            status: Status::Retired,
+            legacy_status: None,
+            audit: Status::Retired,
+            history: vec![],
+            audit_history: None,
+        },
+    ];
+    insert_into(test_statuses::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_statuses::table
+        .order(test_statuses::id)
+        .load::<StatusRow>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    let ids = test_statuses::table
+        .filter(test_statuses::legacy_status.eq(Status::Retired))
+        .filter(test_statuses::audit.eq(Status::Active))
+        .select(test_statuses::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(ids, vec![1]);
+}
@@ -0,0 +1,253 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "ordering_severity", ord)]
+pub enum Severity {
+    // Deliberately not in alphabetical order
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(sql_type = diesel::sql_types::Text, ord)]
+pub enum Label {
+    Urgent,
+    Normal,
+    #[db_enum(other)]
+    Custom(String),
+}
+
+#[test]
+fn ord_follows_declaration_order() {
+    let mut severities = vec![
+        Severity::High,
+        Severity::Low,
+        Severity::Critical,
+        Severity::Medium,
+    ];
+    severities.sort();
+    assert_eq!(
+        severities,
+        [
+            Severity::Low,
+            Severity::Medium,
+            Severity::High,
+            Severity::Critical,
+        ]
+    );
+    assert!(Severity::Medium < Severity::High);
+    assert_eq!(severities.iter().max(), Some(&Severity::Critical));
+
+    let mut labels = vec![
+        Label::Custom("b".to_string()),
+        Label::Normal,
+        Label::Custom("a".to_string()),
+        Label::Urgent,
+    ];
+    labels.sort();
+    assert_eq!(
+        labels,
+        [
+            Label::Urgent,
+            Label::Normal,
+            Label::Custom("a".to_string()),
+            Label::Custom("b".to_string()),
+        ]
+    );
+}
+
+#[cfg(feature = "postgres")]
+table! {
+    use diesel::sql_types::Integer;
+    use super::SeverityMapping;
+    test_ordering {
+        id -> Integer,
+        severity -> SeverityMapping,
+    }
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn pg_aggregates_agree_with_ord() {
+    use diesel::dsl::{max, min};
+
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(Severity::pg_create_type_sql())
+        .unwrap();
+    connection
+        .batch_execute(
+            "CREATE TEMPORARY TABLE test_ordering (
+                id SERIAL PRIMARY KEY,
+                severity ordering_severity NOT NULL
+            )",
+        )
+        .unwrap();
+    let mut severities = vec![Severity::Medium, Severity::Critical, Severity::Low];
+    insert_into(test_ordering::table)
+        .values(
+            severities
+                .iter()
+                .map(|severity| test_ordering::severity.eq(severity))
+                .collect::<Vec<_>>(),
+        )
+        .execute(connection)
+        .unwrap();
+
+    let highest = test_ordering::table
+        .select(max(test_ordering::severity))
+        .get_result::<Option<Severity>>(connection)
+        .unwrap();
+    let lowest = test_ordering::table
+        .select(min(test_ordering::severity))
+        .get_result::<Option<Severity>>(connection)
+        .unwrap();
+    assert_eq!(highest, severities.iter().max().copied());
+    assert_eq!(lowest, severities.iter().min().copied());
+
+    let above_medium = test_ordering::table
+        .select(test_ordering::severity)
+        .filter(test_ordering::severity.gt(Severity::Medium))
+        .load::<Severity>(connection)
+        .unwrap();
+    assert_eq!(above_medium, [Severity::Critical]);
+
+    let sorted = test_ordering::table
+        .select(test_ordering::severity)
+        .order(test_ordering::severity)
+        .load::<Severity>(connection)
+        .unwrap();
+    severities.sort();
+    assert_eq!(sorted, severities);
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(sql_type = diesel::sql_types::Text)]
+pub enum Status {
+    // Deliberately not in alphabetical order
+    Pending,
+    #[db_enum(alias = "enabled")]
+    Active,
+    Archived,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Text};
+    test_declaration_order {
+        id -> Integer,
+        status -> Text,
+    }
+}
+
+// The expression's type can be named, e.g. to share it between queries
+fn status_order() -> StatusDeclarationOrder<test_declaration_order::status> {
+    Status::order_by_declaration(test_declaration_order::status)
+}
+
+#[test]
+fn order_by_declaration() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            "CREATE TEMPORARY TABLE test_declaration_order (
+                id INTEGER PRIMARY KEY,
+                status TEXT NOT NULL
+            );
+            INSERT INTO test_declaration_order (id, status) VALUES
+                (1, 'archived'), (2, 'pending'), (3, 'enabled'), (4, 'active');",
+        )
+        .unwrap();
+    let by_declaration = test_declaration_order::table
+        .select((test_declaration_order::id, test_declaration_order::status))
+        .order_by((status_order(), test_declaration_order::id))
+        .load::<(i32, Status)>(connection)
+        .unwrap();
+    assert_eq!(
+        by_declaration,
+        [
+            (2, Status::Pending),
+            (3, Status::Active),
+            (4, Status::Active),
+            (1, Status::Archived),
+        ]
+    );
+
+    let after_pending = test_declaration_order::table
+        .select(test_declaration_order::id)
+        .filter(Status::order_by_declaration(test_declaration_order::status).gt(0))
+        .order_by(test_declaration_order::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(after_pending, [1, 3, 4]);
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn order_by_declaration_sql() {
+    let query = test_declaration_order::table
+        .select(test_declaration_order::id)
+        .order_by(Status::order_by_declaration(test_declaration_order::status));
+    assert_eq!(
+        diesel::debug_query::<diesel::sqlite::Sqlite, _>(&query).to_string(),
+        "SELECT `test_declaration_order`.`id` FROM `test_declaration_order` ORDER BY \
+         (CASE `test_declaration_order`.`status` WHEN 'pending' THEN 0 WHEN 'active' THEN 1 \
+         WHEN 'enabled' THEN 1 WHEN 'archived' THEN 2 END) -- binds: []"
+    );
+}
+
+// The Postgres type is still mid-migration, so lacks some of these values
+#[derive(Debug, PartialEq, Eq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "ordering_phase")]
+pub enum Phase {
+    #[db_enum(alias = "proposed")]
+    Planned,
+    Done,
+}
+
+#[cfg(feature = "postgres")]
+table! {
+    use diesel::sql_types::Integer;
+    use super::PhaseMapping;
+    test_pg_declaration_order {
+        id -> Integer,
+        phase -> PhaseMapping,
+    }
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn pg_order_by_declaration_with_missing_labels() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            "CREATE TYPE ordering_phase AS ENUM ('done', 'proposed');
+            CREATE TEMPORARY TABLE test_pg_declaration_order (
+                id INTEGER PRIMARY KEY,
+                phase ordering_phase NOT NULL
+            );
+            INSERT INTO test_pg_declaration_order (id, phase) VALUES
+                (1, 'done'), (2, 'proposed');",
+        )
+        .unwrap();
+    let load = |connection: &mut PgConnection| {
+        test_pg_declaration_order::table
+            .select(test_pg_declaration_order::id)
+            .order_by(Phase::order_by_declaration(
+                test_pg_declaration_order::phase,
+            ))
+            .load::<i32>(connection)
+            .unwrap()
+    };
+    // Only the alias is a label of the type, and then only the value
+    assert_eq!(load(connection), [2, 1]);
+    connection
+        .batch_execute(Phase::pg_rename_aliases_sql())
+        .unwrap();
+    assert_eq!(load(connection), [2, 1]);
+}
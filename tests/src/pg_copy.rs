@@ -0,0 +1,114 @@
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel::{copy_from, copy_to};
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "copy_weather")]
+pub enum Weather {
+    Sunny,
+    #[db_enum(rename = "partly cloudy")]
+    PartlyCloudy,
+    Rain,
+    Snow,
+}
+
+table! {
+    use diesel::sql_types::{Array, Integer, Nullable};
+    use super::WeatherMapping;
+    test_pg_copy {
+        id -> Integer,
+        weather -> WeatherMapping,
+        forecast -> Array<WeatherMapping>,
+        previous -> Nullable<WeatherMapping>,
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, PartialEq)]
+#[diesel(table_name = test_pg_copy)]
+struct Reading {
+    id: i32,
+    weather: Weather,
+    forecast: Vec<Weather>,
+    // Diesel's `copy_to` misreads the field after a `NULL`, so this stays last
+    previous: Option<Weather>,
+}
+
+// `COPY FROM` can't look up the array's element type itself
+#[derive(Insertable)]
+#[diesel(table_name = test_pg_copy, treat_none_as_default_value = false)]
+struct NewReading<'a> {
+    id: i32,
+    weather: Weather,
+    forecast: WeatherCopyArray<'a>,
+    previous: Option<Weather>,
+}
+
+fn create_table(connection: &mut PgConnection) {
+    connection
+        .batch_execute(Weather::pg_create_type_sql())
+        .unwrap();
+    connection
+        .batch_execute(
+            "CREATE TEMPORARY TABLE test_pg_copy (
+                id INTEGER PRIMARY KEY,
+                weather copy_weather NOT NULL,
+                forecast copy_weather[] NOT NULL,
+                previous copy_weather
+            )",
+        )
+        .unwrap();
+}
+
+fn sample_data(rows: usize) -> Vec<Reading> {
+    (0..rows)
+        .map(|i| {
+            let weather = |n: usize| Weather::VARIANTS[n % Weather::VARIANTS.len()];
+            Reading {
+                id: i as i32,
+                weather: weather(i),
+                forecast: (0..i % 5).map(|n| weather(i + n)).collect(),
+                previous: (i % 3 != 0).then(|| weather(i + 1)),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn copy_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = sample_data(5000);
+    let metadata = Weather::pg_type_metadata(connection).unwrap();
+    let rows = data
+        .iter()
+        .map(|reading| NewReading {
+            id: reading.id,
+            weather: reading.weather,
+            forecast: WeatherCopyArray::new(&metadata, &reading.forecast),
+            previous: reading.previous,
+        })
+        .collect::<Vec<_>>();
+    let copied = copy_from(test_pg_copy::table)
+        .from_insertable(&rows)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(copied, data.len());
+
+    let mut loaded = copy_to(test_pg_copy::table)
+        .load::<Reading, _>(connection)
+        .unwrap()
+        .collect::<QueryResult<Vec<_>>>()
+        .unwrap();
+    loaded.sort_by_key(|reading| reading.id);
+    assert_eq!(loaded, data);
+
+    // The rows went through the database's own enum type
+    let rainy = test_pg_copy::table
+        .filter(test_pg_copy::weather.eq(Weather::Rain))
+        .count()
+        .get_result::<i64>(connection)
+        .unwrap();
+    assert_eq!(rainy, 1250);
+}
@@ -0,0 +1,81 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "pg_fn_phase")]
+pub enum Phase {
+    // Deliberately not in alphabetical order
+    New,
+    Waxing,
+    Full,
+    Waning,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::PhaseMapping;
+    test_pg_enum_functions {
+        id -> Integer,
+        phase -> PhaseMapping,
+    }
+}
+
+fn create_table(connection: &mut PgConnection) {
+    connection
+        .batch_execute(Phase::pg_create_type_sql())
+        .unwrap();
+    connection
+        .batch_execute(
+            "CREATE TEMPORARY TABLE test_pg_enum_functions (
+                id SERIAL PRIMARY KEY,
+                phase pg_fn_phase NOT NULL
+            );
+            INSERT INTO test_pg_enum_functions (id, phase) VALUES
+                (1, 'full'), (2, 'new'), (3, 'waning');",
+        )
+        .unwrap();
+}
+
+#[test]
+fn enum_range_lists_the_labels() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let range = diesel::select(Phase::pg_enum_range())
+        .get_result::<Vec<Phase>>(connection)
+        .unwrap();
+    assert_eq!(range, Phase::VARIANTS);
+    let (first, last) = diesel::select((Phase::pg_enum_first(), Phase::pg_enum_last()))
+        .get_result::<(Phase, Phase)>(connection)
+        .unwrap();
+    assert_eq!((first, last), (Phase::New, Phase::Waning));
+}
+
+#[test]
+fn enum_functions_in_queries() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let last = test_pg_enum_functions::table
+        .select(test_pg_enum_functions::id)
+        .filter(test_pg_enum_functions::phase.eq(Phase::pg_enum_last()))
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(last, [3]);
+
+    insert_into(test_pg_enum_functions::table)
+        .values((
+            test_pg_enum_functions::id.eq(4),
+            test_pg_enum_functions::phase.eq(Phase::pg_enum_first()),
+        ))
+        .execute(connection)
+        .unwrap();
+    let firsts = test_pg_enum_functions::table
+        .select(test_pg_enum_functions::id)
+        .filter(test_pg_enum_functions::phase.eq(Phase::New))
+        .order(test_pg_enum_functions::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(firsts, [2, 4]);
+}
@@ -0,0 +1,115 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+use diesel_derive_enum::ReadOnlyDbEnumValue;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(sql_type = diesel::sql_types::Text)]
+pub enum Membership {
+    Basic,
+    Premium,
+    #[deprecated(note = "gold memberships are no longer sold")]
+    #[db_enum(read_only)]
+    Gold,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(repr = "i16")]
+pub enum Tier {
+    Low = 1,
+    #[db_enum(read_only)]
+    Legacy = 2,
+}
+
+table! {
+    use diesel::sql_types::{Integer, SmallInt, Text};
+    test_read_only {
+        id -> Integer,
+        membership -> Text,
+        tier -> SmallInt,
+    }
+}
+
+#[derive(Insertable, Queryable, Debug, PartialEq)]
+#[diesel(table_name = test_read_only)]
+struct Member {
+    id: i32,
+    membership: Membership,
+    tier: Tier,
+}
+
+fn create_table(connection: &mut impl SimpleConnection) {
+    connection
+        .batch_execute(
+            "CREATE TEMPORARY TABLE test_read_only (
+                id INTEGER PRIMARY KEY,
+                membership TEXT NOT NULL,
+                tier SMALLINT NOT NULL
+            );
+            INSERT INTO test_read_only (id, membership, tier) VALUES (1, 'gold', 2);",
+        )
+        .unwrap();
+}
+
+#[test]
+#[allow(deprecated)]
+fn read_only_variants_are_read() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let members = test_read_only::table.load::<Member>(connection).unwrap();
+    assert_eq!(
+        members,
+        [Member {
+            id: 1,
+            membership: Membership::Gold,
+            tier: Tier::Legacy,
+        }]
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn read_only_variants_are_not_written() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let err = insert_into(test_read_only::table)
+        .values(&Member {
+            id: 2,
+            membership: Membership::Gold,
+            tier: Tier::Low,
+        })
+        .execute(connection)
+        .unwrap_err();
+    let diesel::result::Error::SerializationError(err) = err else {
+        panic!("expected a serialization error, got {:?}", err);
+    };
+    assert_eq!(
+        err.downcast_ref::<ReadOnlyDbEnumValue>(),
+        Some(&ReadOnlyDbEnumValue {
+            enum_name: "Membership",
+            variant: "Gold",
+        })
+    );
+    let err = insert_into(test_read_only::table)
+        .values(&Member {
+            id: 2,
+            membership: Membership::Basic,
+            tier: Tier::Legacy,
+        })
+        .execute(connection)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Tier::Legacy` is read-only, so can't be written to the database"
+    );
+    insert_into(test_read_only::table)
+        .values(&Member {
+            id: 2,
+            membership: Membership::Premium,
+            tier: Tier::Low,
+        })
+        .execute(connection)
+        .unwrap();
+}
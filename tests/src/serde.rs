@@ -0,0 +1,69 @@
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(serde, rename_all = "camelCase")]
+pub enum Plan {
+    FreeTrial,
+    #[db_enum(rename = "pro")]
+    Professional,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(serde)]
+pub enum Region {
+    EuWest,
+    #[db_enum(other)]
+    Unknown,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(serde)]
+pub enum Label {
+    Urgent,
+    #[db_enum(other)]
+    Custom(String),
+}
+
+#[test]
+fn serialize_db_values() {
+    assert_eq!(
+        serde_json::to_value(Plan::FreeTrial).unwrap(),
+        json!("freeTrial")
+    );
+    assert_eq!(
+        serde_json::to_value(Plan::Professional).unwrap(),
+        json!("pro")
+    );
+    assert_eq!(
+        serde_json::to_value(Label::Custom("later".to_string())).unwrap(),
+        json!("later")
+    );
+}
+
+#[test]
+fn deserialize_db_values() {
+    let plans: Vec<Plan> = serde_json::from_str(r#"["pro", "freeTrial"]"#).unwrap();
+    assert_eq!(plans, [Plan::Professional, Plan::FreeTrial]);
+    let err = serde_json::from_str::<Plan>(r#""Professional""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown variant `Professional`, expected `freeTrial` or `pro` at line 1 column 14"
+    );
+    assert!(serde_json::from_str::<Plan>("1").is_err());
+}
+
+#[test]
+fn deserialize_unknown_values() {
+    assert_eq!(
+        serde_json::from_str::<Region>(r#""us_east""#).unwrap(),
+        Region::Unknown
+    );
+    assert_eq!(
+        serde_json::from_str::<Label>(r#""later""#).unwrap(),
+        Label::Custom("later".to_string())
+    );
+    assert_eq!(
+        serde_json::from_str::<Label>(r#""urgent""#).unwrap(),
+        Label::Urgent
+    );
+}
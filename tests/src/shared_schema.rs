@@ -0,0 +1,122 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+// A schema shared between backends owns the SQL type, so the derive
+// only adds the impls
+pub mod schema {
+    pub mod sql_types {
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "season"))]
+        #[diesel(mysql_type(name = "Enum"))]
+        #[diesel(sqlite_type(name = "Text"))]
+        pub struct Season;
+    }
+
+    diesel::table! {
+        use diesel::sql_types::{Integer, Nullable};
+        use super::sql_types::Season;
+        test_shared_schema {
+            id -> Integer,
+            season -> Season,
+            maybe_season -> Nullable<Season>,
+        }
+    }
+}
+
+use schema::test_shared_schema;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(existing_type_path = crate::shared_schema::schema::sql_types::Season)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_shared_schema)]
+struct Event {
+    id: i32,
+    season: Season,
+    maybe_season: Option<Season>,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TYPE season AS ENUM ('spring', 'summer', 'autumn', 'winter');
+        CREATE TABLE test_shared_schema (
+            id SERIAL PRIMARY KEY,
+            season season NOT NULL,
+            maybe_season season
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_shared_schema (
+            id SERIAL PRIMARY KEY,
+            season enum('spring', 'summer', 'autumn', 'winter') NOT NULL,
+            maybe_season enum('spring', 'summer', 'autumn', 'winter')
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_shared_schema (
+            id SERIAL PRIMARY KEY,
+            season TEXT CHECK(season IN ('spring', 'summer', 'autumn', 'winter')) NOT NULL,
+            maybe_season TEXT CHECK(maybe_season IN ('spring', 'summer', 'autumn', 'winter'))
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn existing_type_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Event {
+            id: 1,
+            season: Season::Autumn,
+            maybe_season: Some(Season::Spring),
+        },
+        Event {
+            id: 2,
+            season: Season::Winter,
+            maybe_season: None,
+        },
+    ];
+    insert_into(test_shared_schema::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_shared_schema::table
+        .order(test_shared_schema::id)
+        .load::<Event>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    let ids = test_shared_schema::table
+        .filter(test_shared_schema::season.eq(Season::Winter))
+        .select(test_shared_schema::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(ids, vec![2]);
+}
@@ -0,0 +1,118 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+use diesel_derive_enum::SkippedDbEnumVariant;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(sql_type = diesel::sql_types::Text, serde)]
+pub enum Shipment {
+    #[db_enum(skip)]
+    Pending {
+        items: u32,
+    },
+    Packed,
+    Shipped,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(repr = "i16")]
+pub enum Priority {
+    Low,
+    #[db_enum(skip)]
+    Unsaved,
+    High,
+}
+
+table! {
+    use diesel::sql_types::{Integer, SmallInt, Text};
+    test_skip {
+        id -> Integer,
+        shipment -> Text,
+        priority -> SmallInt,
+    }
+}
+
+#[derive(Insertable, Queryable, Debug, PartialEq)]
+#[diesel(table_name = test_skip)]
+struct Order {
+    id: i32,
+    shipment: Shipment,
+    priority: Priority,
+}
+
+fn create_table(connection: &mut impl SimpleConnection) {
+    connection
+        .batch_execute(
+            "CREATE TEMPORARY TABLE test_skip (
+                id INTEGER PRIMARY KEY,
+                shipment TEXT NOT NULL,
+                priority SMALLINT NOT NULL
+            );",
+        )
+        .unwrap();
+}
+
+#[test]
+fn skipped_variants_have_no_db_value() {
+    assert_eq!(Shipment::VARIANTS, &[Shipment::Packed, Shipment::Shipped]);
+    assert_eq!(Shipment::DB_VALUES, &["packed", "shipped"]);
+    assert_eq!(Priority::VARIANTS, &[Priority::Low, Priority::High]);
+    // Skipped variants still count towards the implicit discriminants
+    assert_eq!(Priority::DB_VALUES, &[0, 2]);
+    assert_eq!(Shipment::Pending { items: 3 }.as_db_str(), None);
+    assert_eq!(Shipment::Packed.as_db_str(), Some("packed"));
+    assert!(serde_json::to_string(&Shipment::Pending { items: 3 })
+        .unwrap_err()
+        .to_string()
+        .contains("`Shipment::Pending` is marked `skip`"));
+    assert!(serde_json::from_str::<Shipment>("\"pending\"").is_err());
+}
+
+#[test]
+fn skipped_variants_are_not_written() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let err = insert_into(test_skip::table)
+        .values(&Order {
+            id: 1,
+            shipment: Shipment::Pending { items: 3 },
+            priority: Priority::Low,
+        })
+        .execute(connection)
+        .unwrap_err();
+    let diesel::result::Error::SerializationError(err) = err else {
+        panic!("expected a serialization error, got {:?}", err);
+    };
+    assert_eq!(
+        err.downcast_ref::<SkippedDbEnumVariant>(),
+        Some(&SkippedDbEnumVariant {
+            enum_name: "Shipment",
+            variant: "Pending",
+        })
+    );
+    let err = insert_into(test_skip::table)
+        .values(&Order {
+            id: 1,
+            shipment: Shipment::Packed,
+            priority: Priority::Unsaved,
+        })
+        .execute(connection)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Priority::Unsaved` is marked `skip`, so has no database value"
+    );
+
+    let order = Order {
+        id: 1,
+        shipment: Shipment::Shipped,
+        priority: Priority::High,
+    };
+    insert_into(test_skip::table)
+        .values(&order)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(test_skip::table.load::<Order>(connection).unwrap(), [order]);
+}
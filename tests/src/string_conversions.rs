@@ -0,0 +1,56 @@
+use std::convert::TryFrom;
+
+use diesel_derive_enum::UnknownDbEnumValue;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(display, from_str, rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Shipping {
+    Standard,
+    #[db_enum(rename = "next-day")]
+    NextDay,
+    PickUpInStore,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(display, from_str)]
+pub enum Currency {
+    Euro,
+    #[db_enum(other)]
+    Other(String),
+}
+
+#[test]
+fn display_uses_db_values() {
+    assert_eq!(Shipping::Standard.to_string(), "STANDARD");
+    assert_eq!(Shipping::NextDay.to_string(), "next-day");
+    assert_eq!(format!("[{:>10}]", Shipping::NextDay), "[  next-day]");
+    assert_eq!(Currency::Other("yen".to_string()).to_string(), "yen");
+}
+
+#[test]
+fn parse_db_values() {
+    assert_eq!("PICK_UP_IN_STORE".parse(), Ok(Shipping::PickUpInStore));
+    assert_eq!(Shipping::try_from("next-day"), Ok(Shipping::NextDay));
+    assert_eq!(
+        Shipping::try_from("STANDARD".to_string()),
+        Ok(Shipping::Standard)
+    );
+    for variant in Shipping::VARIANTS {
+        assert_eq!(variant.to_string().parse().as_ref(), Ok(variant));
+    }
+    assert_eq!("yen".parse(), Ok(Currency::Other("yen".to_string())));
+}
+
+#[test]
+fn parse_unknown_value() {
+    let err = "NextDay".parse::<Shipping>().unwrap_err();
+    assert_eq!(
+        err,
+        UnknownDbEnumValue {
+            enum_name: "Shipping",
+            sql_type: "ShippingMapping",
+            value: b"NextDay".to_vec(),
+            expected: &["STANDARD", "next-day", "PICK_UP_IN_STORE"],
+        }
+    );
+}
@@ -0,0 +1,155 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+#[cfg(feature = "postgres")]
+use diesel_derive_enum::SchemaReport;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(sql_type = diesel::sql_types::Text)]
+pub enum Color {
+    Red,
+    DarkGreen,
+    #[db_enum(rename = "BLUE")]
+    Blue,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable, Text, VarChar};
+    test_text_column {
+        id -> Integer,
+        color -> Text,
+        short_color -> VarChar,
+        maybe_color -> Nullable<Text>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_text_column)]
+struct Paint {
+    id: i32,
+    color: Color,
+    short_color: Color,
+    maybe_color: Option<Color>,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_text_column (
+            id SERIAL PRIMARY KEY,
+            color TEXT NOT NULL,
+            short_color VARCHAR(16) NOT NULL,
+            maybe_color TEXT
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_text_column (
+            id SERIAL PRIMARY KEY,
+            color TEXT NOT NULL,
+            short_color VARCHAR(16) NOT NULL,
+            maybe_color TEXT
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_text_column (
+            id SERIAL PRIMARY KEY,
+            color TEXT NOT NULL,
+            short_color VARCHAR(16) NOT NULL,
+            maybe_color TEXT
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn text_column_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Paint {
+            id: 1,
+            color: Color::Red,
+            short_color: Color::Blue,
+            maybe_color: Some(Color::DarkGreen),
+        },
+        Paint {
+            id: 2,
+            color: Color::DarkGreen,
+            short_color: Color::Red,
+            maybe_color: None,
+        },
+    ];
+    let ct = insert_into(test_text_column::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_text_column::table
+        .order(test_text_column::id)
+        .load::<Paint>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+}
+
+#[test]
+fn text_column_stores_plain_strings() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    connection
+        .batch_execute(
+            "INSERT INTO test_text_column (id, color, short_color) \
+             VALUES (1, 'dark_green', 'BLUE');",
+        )
+        .unwrap();
+    let ids = test_text_column::table
+        .filter(test_text_column::color.eq(Color::DarkGreen))
+        .filter(test_text_column::short_color.eq(Color::Blue))
+        .select(test_text_column::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(ids, vec![1]);
+    let raw = test_text_column::table
+        .select(test_text_column::color)
+        .first::<String>(connection)
+        .unwrap();
+    assert_eq!(raw, "dark_green");
+}
+
+// Inherent items take precedence over trait ones, so these are only used if the
+// derive generated nothing Postgres-specific for a `Text` column
+#[cfg(feature = "postgres")]
+trait NoPgType {
+    const PG_TYPE_NAME: Option<&'static str> = None;
+
+    fn pg_verify_schema() -> Option<SchemaReport> {
+        None
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl NoPgType for Color {}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn text_column_has_no_pg_type() {
+    assert_eq!(Color::PG_TYPE_NAME, None);
+    assert!(Color::pg_verify_schema().is_none());
+}
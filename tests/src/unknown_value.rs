@@ -0,0 +1,106 @@
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel_derive_enum::UnknownDbEnumValue;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(diesel_type = PlanetMapping, sql_type = diesel::sql_types::Text)]
+pub enum Planet {
+    Mercury,
+    Venus,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Text};
+    use super::PlanetMapping;
+    test_unknown_value {
+        id -> Integer,
+        planet -> PlanetMapping,
+        planet_text -> Text,
+    }
+}
+
+// The database holds 'pluto', which the enum doesn't know about
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TYPE planet AS ENUM ('mercury', 'venus', 'pluto');
+        CREATE TABLE test_unknown_value (
+            id SERIAL PRIMARY KEY,
+            planet planet NOT NULL,
+            planet_text TEXT NOT NULL
+        );
+        INSERT INTO test_unknown_value (id, planet, planet_text) VALUES (1, 'pluto', 'pluto');
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_unknown_value (
+            id SERIAL PRIMARY KEY,
+            planet enum('mercury', 'venus', 'pluto') NOT NULL,
+            planet_text TEXT NOT NULL
+        );
+        INSERT INTO test_unknown_value (id, planet, planet_text) VALUES (1, 'pluto', 'pluto');
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_unknown_value (
+            id SERIAL PRIMARY KEY,
+            planet TEXT NOT NULL,
+            planet_text TEXT NOT NULL
+        );
+        INSERT INTO test_unknown_value (id, planet, planet_text) VALUES (1, 'pluto', 'pluto');
+    "#,
+    )
+    .unwrap();
+}
+
+fn unknown_value(err: diesel::result::Error) -> UnknownDbEnumValue {
+    UnknownDbEnumValue::find(&err)
+        .expect("expected an unknown value error")
+        .clone()
+}
+
+#[test]
+fn unknown_value_error_is_typed() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let err = test_unknown_value::table
+        .select(test_unknown_value::planet)
+        .first::<Planet>(connection)
+        .unwrap_err();
+    assert_eq!(
+        unknown_value(err),
+        UnknownDbEnumValue {
+            enum_name: "Planet",
+            sql_type: "PlanetMapping",
+            value: b"pluto".to_vec(),
+            expected: &["mercury", "venus"],
+        }
+    );
+
+    let err = test_unknown_value::table
+        .select(test_unknown_value::planet_text)
+        .first::<Planet>(connection)
+        .unwrap_err();
+    let unknown = unknown_value(err);
+    assert_eq!(unknown.sql_type, "diesel::sql_types::Text");
+    assert_eq!(
+        unknown.to_string(),
+        r#"Unrecognized enum variant: 'pluto' for `Planet` (diesel::sql_types::Text), expected one of ["mercury", "venus"]"#
+    );
+}
@@ -4,15 +4,17 @@ use diesel::prelude::*;
 use crate::common::get_connection;
 
 #[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
-#[DieselType = "Stylized_Internal_Type"]
-#[PgType = "Stylized_External_Type"]
-#[DbValueStyle = "PascalCase"]
+#[db_enum(
+    diesel_type = Stylized_Internal_Type,
+    pg_type = "Stylized_External_Type",
+    rename_all = "PascalCase"
+)]
 pub enum StylizedEnum {
     FirstVariant,
     secondThing,
     third_item,
     FOURTH_VALUE,
-    #[db_rename = "crazy fifth"]
+    #[db_enum(rename = "crazy fifth")]
     cRaZy_FiFtH,
 }
 
@@ -139,3 +141,54 @@ fn stylized_round_trip() {
         .unwrap();
     assert_eq!(data, inserted);
 }
+
+macro_rules! styled {
+    ($name:ident, $style:literal) => {
+        #[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+        #[db_enum(rename_all = $style)]
+        pub enum $name {
+            FirstVariant,
+            third_item,
+        }
+    };
+}
+
+styled!(Lower, "lowercase");
+styled!(Train, "Train-Case");
+styled!(ScreamingKebab, "SCREAMING-KEBAB-CASE");
+styled!(Dot, "dot.case");
+
+#[test]
+fn case_styles() {
+    assert_eq!(Lower::DB_VALUES, &["firstvariant", "third_item"]);
+    assert_eq!(Train::DB_VALUES, &["First-Variant", "Third-Item"]);
+    assert_eq!(ScreamingKebab::DB_VALUES, &["FIRST-VARIANT", "THIRD-ITEM"]);
+    assert_eq!(Dot::DB_VALUES, &["first.variant", "third.item"]);
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[db_enum(strip_suffix = "Status", prefix = "st_")]
+pub enum OrderStatus {
+    PendingStatus,
+    ShippedStatus,
+    // Only stripped where present
+    Lost,
+    #[db_enum(rename = "gone")]
+    CancelledStatus,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[db_enum(strip_prefix = "Http", suffix = ".method", rename_all = "UPPERCASE")]
+pub enum Method {
+    HttpGet,
+    HttpPost,
+}
+
+#[test]
+fn affixes() {
+    assert_eq!(
+        OrderStatus::DB_VALUES,
+        &["st_pending", "st_shipped", "st_lost", "gone"]
+    );
+    assert_eq!(Method::DB_VALUES, &["GET.method", "POST.method"]);
+}
@@ -0,0 +1,102 @@
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel_derive_enum::SchemaReport;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Light {
+    Red,
+    Amber,
+    Green,
+}
+
+// Each test gets its own table, since MySQL's temporary tables are
+// invisible to `information_schema`
+
+#[cfg(feature = "postgres")]
+fn create_schema(conn: &mut PgConnection, _table: &str, labels: &str) {
+    conn.batch_execute(&format!("CREATE TYPE light AS ENUM ({});", labels))
+        .unwrap();
+}
+
+#[cfg(feature = "postgres")]
+fn verify(conn: &mut PgConnection, _table: &str) -> SchemaReport {
+    Light::pg_verify_schema(conn).unwrap()
+}
+
+#[cfg(feature = "mysql")]
+fn create_schema(conn: &mut MysqlConnection, table: &str, labels: &str) {
+    conn.batch_execute(&format!(
+        "DROP TABLE IF EXISTS {table}; CREATE TABLE {table} (id SERIAL PRIMARY KEY, light enum({labels}));"
+    ))
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+fn verify(conn: &mut MysqlConnection, table: &str) -> SchemaReport {
+    Light::mysql_verify_schema(conn, table, "light").unwrap()
+}
+
+#[cfg(feature = "sqlite")]
+fn create_schema(conn: &mut SqliteConnection, table: &str, labels: &str) {
+    conn.batch_execute(&format!(
+        "CREATE TABLE {table} (id SERIAL PRIMARY KEY, light TEXT CHECK(light IN ({labels})));"
+    ))
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+fn verify(conn: &mut SqliteConnection, table: &str) -> SchemaReport {
+    Light::sqlite_verify_schema(conn, table, "light").unwrap()
+}
+
+#[test]
+fn matching_schema_is_consistent() {
+    let connection = &mut get_connection();
+    create_schema(connection, "test_verify_matching", "'red', 'amber', 'green'");
+    let report = verify(connection, "test_verify_matching");
+    assert!(report.is_consistent(), "{}", report);
+}
+
+#[test]
+fn drifted_schema_is_reported() {
+    let connection = &mut get_connection();
+    create_schema(connection, "test_verify_drifted", "'green', 'red', 'blue'");
+    let report = verify(connection, "test_verify_drifted");
+    assert_eq!(
+        report,
+        SchemaReport {
+            enum_name: "Light",
+            missing_in_database: vec!["amber".to_string()],
+            missing_in_enum: vec!["blue".to_string()],
+            order_differs: true,
+        }
+    );
+    assert_eq!(
+        report.to_string(),
+        r#"`Light` doesn't match the database: missing in the database: ["amber"]; missing in the enum: ["blue"]; values are declared in a different order;"#
+    );
+}
+
+#[test]
+fn missing_schema_is_reported() {
+    let connection = &mut get_connection();
+    let report = verify(connection, "test_verify_missing");
+    assert_eq!(report.missing_in_database, vec!["red", "amber", "green"]);
+    assert!(report.missing_in_enum.is_empty());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn generated_check_constraint_is_consistent() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(&format!(
+            "CREATE TABLE test_verify_generated (id SERIAL PRIMARY KEY, light TEXT NOT NULL {})",
+            Light::sqlite_check_constraint_sql("light")
+        ))
+        .unwrap();
+    let report = Light::sqlite_verify_schema(connection, "test_verify_generated", "light").unwrap();
+    assert!(report.is_consistent(), "{}", report);
+}
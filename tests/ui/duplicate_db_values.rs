@@ -0,0 +1,42 @@
+#![allow(non_camel_case_types)]
+
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+pub enum SameStylizedValue {
+    FooBar,
+    Foo_Bar,
+}
+
+#[derive(DbEnum)]
+pub enum RenameClashes {
+    Foo,
+    #[db_enum(rename = "foo")]
+    Bar,
+}
+
+#[derive(DbEnum)]
+pub enum EmptyValue {
+    #[db_enum(rename = "")]
+    Foo,
+}
+
+#[derive(DbEnum)]
+pub enum BackendRenameClashes {
+    Foo,
+    #[db_enum(rename(mysql = "foo"))]
+    Bar,
+}
+
+#[derive(DbEnum)]
+pub enum AliasClashes {
+    Foo,
+    #[db_enum(alias = "foo")]
+    Bar,
+    #[db_enum(alias = "old")]
+    Baz,
+    #[db_enum(alias = "old")]
+    Quxx,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+pub enum HasFields {
+    Foo,
+    Bar(i32),
+    Baz { quxx: String },
+}
+
+fn main() {}
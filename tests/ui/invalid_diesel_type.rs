@@ -0,0 +1,15 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(diesel_type = "Not An Ident")]
+pub enum BadMapping {
+    Foo,
+}
+
+#[derive(DbEnum)]
+#[db_enum(diesel_type = crate::Mapping)]
+pub enum BadMappingPath {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+pub enum TwoFallbacks {
+    Foo,
+    #[db_enum(other)]
+    Bar,
+    #[db_enum(other)]
+    Baz,
+}
+
+#[derive(DbEnum)]
+pub enum CatchAllNotString {
+    Foo,
+    #[db_enum(other)]
+    Unknown(i32),
+}
+
+#[derive(DbEnum)]
+pub enum CatchAllTooManyFields {
+    Foo,
+    #[db_enum(other)]
+    Unknown(String, String),
+}
+
+#[derive(DbEnum)]
+pub enum CatchAllRenamed {
+    Foo,
+    #[db_enum(other, rename = "unknown")]
+    Unknown(String),
+}
+
+#[derive(DbEnum)]
+pub enum CatchAllAliased {
+    Foo,
+    #[db_enum(other, alias = "unknown")]
+    Unknown(String),
+}
+
+fn main() {}
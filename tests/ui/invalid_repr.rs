@@ -0,0 +1,53 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(repr = "u8")]
+pub enum UnsupportedRepr {
+    Foo,
+}
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i16")]
+pub enum OutOfRange {
+    Foo = 40000,
+    #[db_enum(value = -40000)]
+    Bar,
+}
+
+const BASE: isize = 3;
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i32")]
+pub enum BadValues {
+    Foo = 1,
+    #[db_enum(value = 1)]
+    Bar,
+    Baz = BASE,
+    #[db_enum(value = "four")]
+    Quxx,
+}
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i32", diesel_type = IgnoredMapping, rename_all = "camelCase", prefix = "x", display, from_str, ord)]
+pub enum TextOptions {
+    #[db_enum(rename = "foo")]
+    Foo,
+    #[db_enum(alias = "bar")]
+    Bar,
+}
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i64")]
+pub enum CatchAll {
+    Foo,
+    #[db_enum(other)]
+    Unknown(String),
+}
+
+#[derive(DbEnum)]
+pub enum ValueWithoutRepr {
+    #[db_enum(value = 1)]
+    Foo,
+}
+
+fn main() {}
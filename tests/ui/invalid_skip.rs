@@ -0,0 +1,36 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+pub enum SkipWithValue {
+    Foo,
+    #[db_enum(skip, rename = "bar", alias = "baz")]
+    Bar,
+    #[db_enum(skip, other, read_only)]
+    Unknown(String),
+}
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i16")]
+pub enum SkipWithIntValue {
+    Foo,
+    #[db_enum(skip, value = 3)]
+    Bar,
+}
+
+#[derive(DbEnum, PartialEq, Eq)]
+#[db_enum(ord)]
+pub enum SkipWithOrd {
+    Foo,
+    #[db_enum(skip)]
+    Bar(u32),
+}
+
+#[derive(DbEnum)]
+#[db_enum(display)]
+pub enum SkipWithDisplay {
+    Foo,
+    #[db_enum(skip)]
+    Bar,
+}
+
+fn main() {}
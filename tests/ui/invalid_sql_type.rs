@@ -0,0 +1,15 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(sql_type = diesel::sql_types::Integer)]
+pub enum NotText {
+    Foo,
+}
+
+#[derive(DbEnum)]
+#[db_enum(sql_type = Text, repr = "i32")]
+pub enum WithRepr {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[DbValueStyle(snake_case)]
+pub enum BadContainerAttribute {
+    Foo,
+}
+
+#[derive(DbEnum)]
+pub enum BadVariantAttribute {
+    #[db_rename = 1]
+    Foo,
+    #[db_rename]
+    Bar,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(diesel_type = "crate::Mapping", rename_all = "SHOUTING")]
+pub enum ManyProblems {
+    Foo(u8),
+    #[db_enum(rename(bar))]
+    Bar,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+pub struct NotAnEnum {
+    foo: i32,
+}
+
+fn main() {}
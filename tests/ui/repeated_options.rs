@@ -0,0 +1,34 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(pg_type = "first", pg_type = "second")]
+pub enum RepeatedOption {
+    Foo,
+}
+
+#[derive(DbEnum)]
+#[db_enum(existing_type_path = RemoteMapping, existing_type_path = RemoteMapping)]
+pub enum RepeatedPath {
+    Foo,
+}
+
+#[derive(DbEnum)]
+#[DbValueStyle = "camelCase"]
+#[db_enum(rename_all = "snake_case")]
+pub enum LegacyAndNew {
+    #[db_rename = "foo"]
+    #[db_enum(rename = "bar")]
+    Foo,
+}
+
+#[derive(DbEnum)]
+pub enum RepeatedBackend {
+    #[db_enum(rename(sqlite = "foo", sqlite = "bar"))]
+    Foo,
+    #[db_enum(alias = "old", alias = "old")]
+    Bar,
+    #[db_enum(read_only, read_only)]
+    Baz,
+}
+
+fn main() {}
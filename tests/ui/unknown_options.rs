@@ -0,0 +1,21 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(pg_typ = "unknown")]
+pub enum UnknownContainerOption {
+    Foo,
+}
+
+#[derive(DbEnum)]
+pub enum UnknownVariantOption {
+    #[db_enum(renamed = "foo")]
+    Foo,
+}
+
+#[derive(DbEnum)]
+#[db_enum(rename_all(oracle = "camelCase"))]
+pub enum UnknownBackend {
+    Foo,
+}
+
+fn main() {}
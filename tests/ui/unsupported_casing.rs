@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(rename_all = "Title Case")]
+pub enum BadStyle {
+    Foo,
+    Bar,
+}
+
+fn main() {}